@@ -0,0 +1,213 @@
+/// Generates the module that compiles every solution in `src/bin` into the `advent_of_code_runner` binary.
+/// Each solution is included as a module and its `SOLUTION` entry is collected into `SOLUTIONS`.
+/// Lints of the solutions are reported by their own binaries, so they are silenced here.
+/// The other commands only need to know which days exist, they get the list `DAYS` without compiling any solution.
+/// It also generates a test per named example of each day, which solutions include with `example_tests!`.
+/// The year of the config is passed on as `AOC_YEAR`, the default year of `solution!(day)`.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let config = Config::read(&manifest_dir);
+
+    if let Some(year) = config.value("year", "AOC_YEAR") {
+        println!("cargo:rustc-env=AOC_YEAR={year}");
+    }
+
+    let bin_dir = manifest_dir.join("src/bin");
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter_map(|name| name.strip_suffix(".rs").map(String::from))
+                .filter(|name| is_solution_name(name))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort();
+
+    let mut out = String::new();
+
+    for name in &names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        out += "#[cfg(not(test))]\n";
+        out += &format!("#[path = {:?}]\n", path.display().to_string());
+        out += "#[allow(warnings, clippy::all, clippy::pedantic)]\n";
+        out += &format!("mod solution_{name};\n\n");
+    }
+
+    // solutions are left out of test builds, they are tested in their own binaries.
+    out += "#[cfg(not(test))]\n";
+    out += "pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[\n";
+    for name in &names {
+        out += &format!("    solution_{name}::SOLUTION,\n");
+    }
+    out += "];\n\n";
+    out += "#[cfg(test)]\n";
+    out += "pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[];\n";
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), out).unwrap();
+
+    let mut out =
+        String::from("pub const DAYS: &[(advent_of_code::Year, advent_of_code::Day)] = &[\n");
+    for name in &names {
+        let (year, day) = name.split_once('_').unwrap();
+        let (year, day): (u16, u8) = (year.parse().unwrap(), day.parse().unwrap());
+        // a file with an invalid year or day is reported by its own binary.
+        if (2015..=9999).contains(&year) && (1..=25).contains(&day) {
+            out += &format!("    (advent_of_code::year!({year}), advent_of_code::day!({day})),\n");
+        }
+    }
+    out += "];\n";
+    fs::write(out_dir.join("days.rs"), out).unwrap();
+
+    let data_dir = manifest_dir.join(
+        config
+            .value("data_dir", "AOC_DATA_DIR")
+            .unwrap_or_else(|| "data".into()),
+    );
+    write_example_tests(&data_dir, &out_dir.join("examples"), &names);
+}
+
+/// The settings of `aoc.toml` that the build depends on, see `template::config`.
+struct Config(toml::Table);
+
+impl Config {
+    fn read(manifest_dir: &Path) -> Self {
+        let path =
+            env::var("AOC_CONFIG").map_or_else(|_| manifest_dir.join("aoc.toml"), PathBuf::from);
+        println!("cargo:rerun-if-env-changed=AOC_CONFIG");
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        // an invalid config is reported by the binary instead of failing the build.
+        let table = fs::read_to_string(path)
+            .ok()
+            .and_then(|s| toml::from_str(&s).ok())
+            .unwrap_or_default();
+        Self(table)
+    }
+
+    /// A setting of the config, overridden by the environment variable `var`.
+    fn value(&self, key: &str, var: &str) -> Option<String> {
+        println!("cargo:rerun-if-env-changed={var}");
+
+        if let Ok(value) = env::var(var) {
+            return Some(value);
+        }
+
+        match self.0.get(key)? {
+            toml::Value::String(s) => Some(s.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+/// Writes `examples/{name}.rs` for every solution, with one test per example of the day.
+fn write_example_tests(data_dir: &Path, out_dir: &Path, names: &[String]) {
+    fs::create_dir_all(out_dir).unwrap();
+
+    let mut years: Vec<&str> = names.iter().map(|name| &name[..4]).collect();
+    years.dedup();
+    for year in years {
+        println!(
+            "cargo:rerun-if-changed={}",
+            data_dir.join(year).join("examples").display()
+        );
+    }
+
+    for name in names {
+        let (year, day) = name.split_once('_').unwrap();
+        let path = data_dir.join(format!("{year}/examples/{day}.toml"));
+
+        let out = match fs::read_to_string(&path) {
+            Ok(s) => match example_names(&s) {
+                Some(example_names) => example_tests(&example_names),
+                // the examples are reported as invalid by the test instead of failing the build.
+                None => invalid_examples_test(),
+            },
+            Err(_) => String::new(),
+        };
+
+        fs::write(out_dir.join(format!("{name}.rs")), out).unwrap();
+    }
+}
+
+/// The names of all examples in an examples file, [`None`] if it is not valid.
+fn example_names(s: &str) -> Option<Vec<String>> {
+    let table: toml::Table = toml::from_str(s).ok()?;
+
+    match table.get("example") {
+        None => Some(vec![]),
+        Some(examples) => examples
+            .as_array()?
+            .iter()
+            .map(|example| Some(example.get("name")?.as_str()?.to_string()))
+            .collect(),
+    }
+}
+
+fn example_tests(names: &[String]) -> String {
+    let mut out = String::new();
+    let mut test_names: Vec<String> = vec![];
+
+    for name in names {
+        let mut test_name = format!("example_{}", sanitize(name));
+        if test_names.contains(&test_name) {
+            test_name = format!("{test_name}_{}", test_names.len() + 1);
+        }
+
+        out += "#[test]\n";
+        out += &format!("fn {test_name}() {{\n");
+        out += &format!(
+            "    advent_of_code::template::examples::check(&super::SOLUTION, {name:?});\n"
+        );
+        out += "}\n\n";
+
+        test_names.push(test_name);
+    }
+
+    out
+}
+
+fn invalid_examples_test() -> String {
+    let mut out = String::new();
+    out += "#[test]\n";
+    out += "fn examples() {\n";
+    out +=
+        "    if let Err(e) = advent_of_code::template::examples::load(super::YEAR, super::DAY) {\n";
+    out += "        panic!(\"{e}\");\n";
+    out += "    }\n";
+    out += "}\n";
+    out
+}
+
+/// Turns an example name into an identifier, e.g. `Part two (small)` into `part_two_small`.
+fn sanitize(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Whether a bin file name has the `{year}_{day}` form of a solution.
+fn is_solution_name(name: &str) -> bool {
+    match name.split_once('_') {
+        Some((year, day)) => {
+            year.len() == 4
+                && day.len() == 2
+                && year.chars().all(|c| c.is_ascii_digit())
+                && day.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
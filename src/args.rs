@@ -0,0 +1,337 @@
+/// Arguments of the commands, shared by the `advent_of_code` binary and the runner of the solutions.
+use std::{env, ffi::OsString, time::Duration};
+
+use advent_of_code::template::cli::{self, Shell};
+use advent_of_code::template::commands::scaffold;
+use advent_of_code::template::config;
+use advent_of_code::template::input::{InputSource, EXAMPLE_FLAG, INPUT_FLAG, STDIN_FLAG};
+use advent_of_code::template::readme_benchmarks::{self, TableOptions};
+use advent_of_code::template::report::REPORT_PATH;
+use advent_of_code::template::runner::{parse_timeout, TIMEOUT_FLAG};
+use advent_of_code::{Day, Year};
+
+// each binary only reads the arguments of the commands it handles.
+#[allow(dead_code)]
+pub enum AppArguments {
+    Download {
+        year: Year,
+        day: Day,
+    },
+    Read {
+        year: Year,
+        day: Day,
+    },
+    Scaffold {
+        year: Year,
+        day: Day,
+        template: Option<String>,
+        return_type: Option<String>,
+    },
+    Start {
+        year: Year,
+        day: Day,
+        wait: bool,
+        template: Option<String>,
+        return_type: Option<String>,
+    },
+    Examples {
+        year: Year,
+        day: Day,
+        pick: Option<Vec<usize>>,
+    },
+    Solve {
+        year: Year,
+        day: Day,
+        release: bool,
+        time: bool,
+        submit: Option<u8>,
+        watch: bool,
+        timeout: Option<Duration>,
+        source: InputSource,
+    },
+    Submit {
+        flush: bool,
+    },
+    All {
+        year: Option<Year>,
+        release: bool,
+        time: bool,
+        json: bool,
+        jobs: usize,
+        timeout: Option<Duration>,
+        table: TableOptions,
+    },
+    Verify {
+        year: Option<Year>,
+    },
+    Stars,
+    Report {
+        year: Option<Year>,
+        redact: bool,
+        output: String,
+    },
+    Completions {
+        shell: Shell,
+    },
+    /// Help of the binary or of a command.
+    Help(String),
+    BenchCompare {
+        baseline: Option<String>,
+        threshold: f64,
+    },
+}
+
+/// The year passed with `--year`, falling back to the `year` of the config.
+fn year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+    match args.opt_value_from_str("--year")?.or(config::get().year) {
+        Some(year) => Ok(year),
+        None => Err(format!(
+            "No year specified, pass `--year` or set `year` in `{}`.",
+            config::path()
+        )
+        .into()),
+    }
+}
+
+/// Takes the `--set <key>=<value>` overrides out of `raw` and exports them to the environment,
+/// where the config of this run and of the solutions it runs picks them up.
+fn apply_settings(raw: Vec<String>) -> Result<Vec<String>, String> {
+    let mut rest = vec![];
+    let mut raw = raw.into_iter();
+
+    while let Some(arg) = raw.next() {
+        let setting = if arg == cli::SET_FLAG {
+            raw.next()
+                .ok_or_else(|| format!("`{}` is missing its value", cli::SET_FLAG))?
+        } else if let Some(setting) = arg.strip_prefix(&format!("{}=", cli::SET_FLAG)) {
+            setting.to_string()
+        } else {
+            rest.push(arg);
+            continue;
+        };
+
+        let Some((key, value)) = setting.split_once('=') else {
+            return Err(format!(
+                "invalid setting `{setting}`, expecting `<key>=<value>`"
+            ));
+        };
+        let Some(var) = config::env_var(key) else {
+            return Err(config::Error::UnknownSetting(key.into()).to_string());
+        };
+        env::set_var(var, value);
+    }
+
+    Ok(rest)
+}
+
+/// The input that `solve` runs against, selected with `--input <path>`, `--stdin` or `--example`.
+fn input_source(
+    args: &mut pico_args::Arguments,
+) -> Result<InputSource, Box<dyn std::error::Error>> {
+    let path: Option<String> = args.opt_value_from_str(INPUT_FLAG)?;
+    let is_stdin = args.contains(STDIN_FLAG);
+    let is_example = args.contains(EXAMPLE_FLAG);
+
+    let mut flags = vec![];
+    if let Some(path) = path {
+        flags.extend([INPUT_FLAG.to_string(), path]);
+    }
+    if is_stdin {
+        flags.push(STDIN_FLAG.into());
+    }
+    if is_example {
+        flags.push(EXAMPLE_FLAG.into());
+    }
+
+    Ok(InputSource::from_args(&flags)?)
+}
+
+/// A comma-separated list of block numbers, e.g. `1,3`.
+fn parse_pick(s: &str) -> Result<Vec<usize>, String> {
+    s.split(',')
+        .map(|x| {
+            x.trim()
+                .parse()
+                .map_err(|_| format!("invalid block number `{x}`"))
+        })
+        .collect()
+}
+
+/// The submitted part, `1` or `2`.
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part `{s}`, expecting `1` or `2`")),
+    }
+}
+
+pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    let raw = apply_settings(env::args().skip(1).collect())?;
+    let is_help = |arg: &String| arg == "--help" || arg == "-h";
+
+    let Some(name) = raw.first() else {
+        return Err(format!("no command specified.\n\n{}", cli::help()).into());
+    };
+
+    if is_help(name) || name == "help" {
+        return Ok(match raw.get(1).and_then(|name| cli::find(name)) {
+            Some(command) => AppArguments::Help(command.help()),
+            None => AppArguments::Help(cli::help()),
+        });
+    }
+
+    let Some(command) = cli::find(name) else {
+        return Err(format!(
+            "unknown command `{name}`, run `advent_of_code --help` to list all commands."
+        )
+        .into());
+    };
+
+    if raw.iter().skip(1).any(is_help) {
+        return Ok(AppArguments::Help(command.help()));
+    }
+
+    command.check_flags(&raw[1..])?;
+
+    let config =
+        config::load().map_err(|e| format!("invalid config \"{}\": {e}", config::path()))?;
+    let defaults = config.defaults.get(name).cloned().unwrap_or_default();
+    config::init(config);
+
+    let raw = command.with_defaults(&defaults, &raw[1..])?;
+    let mut args = pico_args::Arguments::from_vec(raw.into_iter().map(OsString::from).collect());
+    let app_args = parse_command(name, &mut args).map_err(|e| {
+        let e = match e.downcast_ref::<pico_args::Error>() {
+            Some(pico_args::Error::MissingArgument) => {
+                let names: Vec<_> = command.positionals.iter().map(|p| p.name).collect();
+                format!("missing argument `<{}>`", names.join("> <"))
+            }
+            _ => e.to_string(),
+        };
+        format!("{e}\n\n{}", command.usage())
+    })?;
+
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        return Err(format!(
+            "unexpected argument(s) {remaining:?} for `{name}`\n\n{}",
+            command.usage()
+        )
+        .into());
+    }
+
+    Ok(app_args)
+}
+
+fn parse_command(
+    name: &str,
+    args: &mut pico_args::Arguments,
+) -> Result<AppArguments, Box<dyn std::error::Error>> {
+    let app_args = match name {
+        "all" => AppArguments::All {
+            year: args.opt_value_from_str("--year")?,
+            release: args.contains("--release"),
+            time: args.contains("--time"),
+            json: args.contains("--json"),
+            jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+            timeout: args.opt_value_from_fn(TIMEOUT_FLAG, parse_timeout)?,
+            table: TableOptions {
+                columns: args
+                    .opt_value_from_fn("--columns", readme_benchmarks::parse_columns)?
+                    .unwrap_or_default(),
+                sort: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+                chart: args.contains("--chart"),
+            },
+        },
+        "download" => AppArguments::Download {
+            year: year(args)?,
+            day: args.free_from_str()?,
+        },
+        "read" => AppArguments::Read {
+            year: year(args)?,
+            day: args.free_from_str()?,
+        },
+        "scaffold" => AppArguments::Scaffold {
+            year: year(args)?,
+            template: args.opt_value_from_str("--template")?,
+            return_type: args.opt_value_from_fn("--type", scaffold::parse_return_type)?,
+            day: args.free_from_str()?,
+        },
+        "start" => AppArguments::Start {
+            year: year(args)?,
+            wait: args.contains("--wait"),
+            template: args.opt_value_from_str("--template")?,
+            return_type: args.opt_value_from_fn("--type", scaffold::parse_return_type)?,
+            day: args.free_from_str()?,
+        },
+        "examples" => AppArguments::Examples {
+            year: year(args)?,
+            pick: args.opt_value_from_fn("--pick", parse_pick)?,
+            day: args.free_from_str()?,
+        },
+        "bench" => match args.free_from_str::<String>()?.as_str() {
+            "compare" => AppArguments::BenchCompare {
+                baseline: args.opt_value_from_str("--baseline")?,
+                threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
+            },
+            x => return Err(format!("unknown bench mode `{x}`, expecting `compare`").into()),
+        },
+        "verify" => AppArguments::Verify {
+            year: args.opt_value_from_str("--year")?,
+        },
+        "stars" => AppArguments::Stars,
+        "report" => AppArguments::Report {
+            year: args.opt_value_from_str("--year")?,
+            redact: args.contains("--redact"),
+            output: args
+                .opt_value_from_str("--output")?
+                .unwrap_or_else(|| REPORT_PATH.into()),
+        },
+        "solve" => AppArguments::Solve {
+            year: year(args)?,
+            release: args.contains("--release"),
+            submit: args.opt_value_from_fn("--submit", parse_part)?,
+            time: args.contains("--time"),
+            watch: args.contains("--watch"),
+            timeout: args.opt_value_from_fn(TIMEOUT_FLAG, parse_timeout)?,
+            source: input_source(args)?,
+            day: args.free_from_str()?,
+        },
+        "submit" => AppArguments::Submit {
+            flush: args.contains("--flush"),
+        },
+        "completions" => AppArguments::Completions {
+            shell: args.free_from_str()?,
+        },
+        x => return Err(format!("unknown command `{x}`").into()),
+    };
+
+    if let AppArguments::Solve {
+        watch: true,
+        submit,
+        time,
+        source,
+        ..
+    } = &app_args
+    {
+        // watch mode always runs the puzzle input once per change and only shows the answers.
+        let flag = if submit.is_some() {
+            Some("--submit")
+        } else if *time {
+            Some("--time")
+        } else {
+            match source {
+                InputSource::Puzzle => None,
+                InputSource::Example => Some(EXAMPLE_FLAG),
+                InputSource::File(_) => Some(INPUT_FLAG),
+                InputSource::Stdin => Some(STDIN_FLAG),
+            }
+        };
+        if let Some(flag) = flag {
+            return Err(format!("`--watch` can not be combined with `{flag}`").into());
+        }
+    }
+
+    Ok(app_args)
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let reqs = HashMap::from([("red", 12), ("green", 13), ("blue", 14)]);
@@ -100,13 +100,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(2286));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let schematic = input.trim_end().lines().collect::<Vec<&str>>();
@@ -75,13 +75,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(467835));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let cards = input
@@ -88,13 +88,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(30));
     }
 }
@@ -1,6 +1,6 @@
 use std::{ops::Range, slice::SliceIndex};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5);
 
 #[derive(Debug)]
 struct MapEntry {
@@ -207,13 +207,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(46));
     }
 }
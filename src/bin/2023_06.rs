@@ -1,4 +1,4 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let lines = input
@@ -56,13 +56,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(71503));
     }
 }
@@ -1,6 +1,6 @@
 use num::Integer;
 use std::collections::HashMap;
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let splits = input.trim_end().split("\n\n").collect::<Vec<&str>>();
@@ -86,13 +86,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2023, 11);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tile {
@@ -136,13 +136,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(14);
+advent_of_code::solution!(2023, 14);
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Tile {
@@ -115,13 +115,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(64));
     }
 }
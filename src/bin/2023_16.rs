@@ -1,4 +1,4 @@
-advent_of_code::solution!(16);
+advent_of_code::solution!(2023, 16);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tile {
@@ -219,13 +219,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(51));
     }
 }
@@ -1,7 +1,7 @@
 use pathfinding::matrix::{directions, Matrix};
 use pathfinding::prelude::astar;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(2023, 17);
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 struct State {
@@ -111,13 +111,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(102));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(94));
     }
 }
@@ -10,7 +10,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(2023, 19);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Destination {
@@ -312,13 +312,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(19114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(167409079868000));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2023, 20);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Module<'a> {
@@ -205,13 +205,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(11687500));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
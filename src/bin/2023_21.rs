@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(2023, 21);
 
 #[derive(Debug, PartialEq)]
 enum Tile {
@@ -175,13 +175,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
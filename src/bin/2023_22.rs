@@ -3,7 +3,7 @@ use std::{
     ops::RangeInclusive,
 };
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2023, 22);
 
 #[derive(Debug, Clone)]
 struct Cube {
@@ -159,13 +159,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(7));
     }
 }
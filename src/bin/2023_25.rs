@@ -4,7 +4,7 @@ use rustworkx_core::petgraph::graph::UnGraph;
 use rustworkx_core::connectivity::stoer_wagner_min_cut;
 use rustworkx_core::Result;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2023, 25);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut node_translate = HashMap::<&str, u32>::new();
@@ -59,13 +59,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Option::Some(54));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 }
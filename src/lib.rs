@@ -1,4 +1,7 @@
 mod day;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use template::solution::Solution;
+pub use year::*;
@@ -1,92 +1,85 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
-use args::{parse, AppArguments};
-
-mod args {
-    use std::process;
-
-    use advent_of_code::Day;
-
-    pub enum AppArguments {
-        Download {
-            day: Day,
-        },
-        Read {
-            day: Day,
-        },
-        Scaffold {
-            day: Day,
-        },
-        Solve {
-            day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-            time: bool,
-        },
-    }
+use std::env;
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+use advent_of_code::template::cli;
+use advent_of_code::template::commands::{
+    bench, download, examples, read, report, scaffold, solve, stars, start, submit,
+};
+use advent_of_code::template::registry;
+use advent_of_code::Year;
+use args::{parse, AppArguments};
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
+/// The days in `src/bin`, listed by `build.rs`. Their solutions are compiled into the runner binary only.
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+mod args;
 
-        Ok(app_args)
-    }
+/// All years with at least one day, in ascending order.
+fn years() -> Vec<Year> {
+    let mut years: Vec<Year> = days::DAYS.iter().map(|&(year, _)| year).collect();
+    years.dedup();
+    years
 }
 
 fn main() {
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            std::process::exit(cli::USAGE_EXIT_CODE);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Help(help) => println!("{help}"),
+            AppArguments::Completions { shell } => print!("{}", cli::completions(shell)),
+            // the commands that run solutions are handled by the runner, see `src/runner.rs`.
+            AppArguments::All { .. } | AppArguments::Verify { .. } => {
+                registry::run_in_runner(env::args_os().skip(1));
+            }
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Scaffold {
+                year,
+                day,
+                template,
+                return_type,
+            } => scaffold::handle(year, day, template.as_deref(), return_type.as_deref()),
+            AppArguments::Start {
+                year,
+                day,
+                wait,
+                template,
+                return_type,
+            } => start::handle(year, day, wait, template.as_deref(), return_type.as_deref()),
+            AppArguments::Examples { year, day, pick } => {
+                examples::handle(year, day, pick.as_deref());
+            }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                watch,
+                timeout,
+                source,
+            } => {
+                if watch {
+                    solve::watch(year, day, release, timeout);
+                } else {
+                    solve::handle(year, day, release, time, submit, timeout, &source);
+                }
+            }
+            AppArguments::Submit { flush } => submit::handle(flush),
+            AppArguments::Stars => stars::handle(&years()),
+            AppArguments::Report {
+                year,
+                redact,
+                output,
+            } => report::handle(days::DAYS, year, redact, &output),
+            AppArguments::BenchCompare {
+                baseline,
+                threshold,
+            } => bench::compare(baseline.as_deref(), threshold),
         },
     };
 }
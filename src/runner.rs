@@ -0,0 +1,50 @@
+use advent_of_code::template::cli;
+use advent_of_code::template::commands::{all, verify};
+use advent_of_code::template::registry::Registry;
+use args::{parse, AppArguments};
+
+/// Solutions of all days, compiled into this binary by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// The runner takes the same arguments as `advent_of_code`, but only handles `all` and `verify`.
+mod args;
+
+fn registry() -> Registry {
+    Registry::new(solutions::SOLUTIONS.iter().copied())
+}
+
+fn main() {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(cli::USAGE_EXIT_CODE);
+        }
+        Ok(args) => match args {
+            AppArguments::All {
+                year,
+                release,
+                time,
+                json,
+                jobs,
+                timeout,
+                table,
+            } => all::handle(
+                &registry(),
+                year,
+                release,
+                time,
+                json,
+                jobs,
+                timeout,
+                &table,
+            ),
+            AppArguments::Verify { year } => verify::handle(&registry(), year),
+            _ => {
+                eprintln!("Error: the runner only runs `all` and `verify`, use `advent_of_code` for the other commands.");
+                std::process::exit(cli::USAGE_EXIT_CODE);
+            }
+        },
+    };
+}
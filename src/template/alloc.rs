@@ -0,0 +1,158 @@
+/// Opt-in heap statistics of solution parts.
+/// With the `alloc_stats` feature enabled, a counting allocator is installed as the global allocator of every binary.
+/// Counters are kept per thread, so parts that run concurrently do not affect each other's numbers.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "alloc_stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Heap usage of one execution of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// The largest amount of heap memory that was in use at once, on top of what was in use before.
+    pub peak_bytes: u64,
+}
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    static CURRENT: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+/// Wraps the system allocator and counts the allocations of the current thread.
+pub struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let size = i64::try_from(size).unwrap_or(i64::MAX);
+    // counters may already be gone while a thread shuts down, such allocations are not counted.
+    let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+    let _ = BYTES.try_with(|c| c.set(c.get() + size.unsigned_abs()));
+    let _ = CURRENT.try_with(|current| {
+        current.set(current.get() + size);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let size = i64::try_from(size).unwrap_or(i64::MAX);
+    let _ = CURRENT.try_with(|c| c.set(c.get() - size));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether the counting allocator is installed.
+#[must_use]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc_stats")
+}
+
+/// Runs `f` and measures its heap usage on the current thread.
+/// Returns [`None`] as statistics if the counting allocator is not installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.get();
+    let bytes = BYTES.get();
+    let current = CURRENT.get();
+    PEAK.set(current);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.get() - allocations,
+        bytes: BYTES.get() - bytes,
+        peak_bytes: (PEAK.get() - current).unsigned_abs(),
+    };
+
+    (result, Some(stats))
+}
+
+/// Formats an amount of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, record_alloc, record_dealloc, ALLOCATIONS, BYTES, CURRENT, PEAK};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn tracks_peak_of_current_thread() {
+        let (allocations, bytes, current) = (ALLOCATIONS.get(), BYTES.get(), CURRENT.get());
+        PEAK.set(current);
+
+        record_alloc(100);
+        record_alloc(50);
+        record_dealloc(100);
+        record_alloc(20);
+
+        assert_eq!(ALLOCATIONS.get() - allocations, 3);
+        assert_eq!(BYTES.get() - bytes, 170);
+        assert_eq!(CURRENT.get() - current, 70);
+        assert_eq!(PEAK.get() - current, 150);
+
+        record_dealloc(70);
+    }
+}
@@ -0,0 +1,302 @@
+/// Module that keeps a ledger of submitted answers and their verdicts.
+/// The ledger is used to guard against submissions that are known to be wrong.
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::aoc_client::{Hint, SubmissionOutcome};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answer ledger: {e}"),
+            Error::IO(e) => write!(f, "could not access answer ledger: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+}
+
+impl From<&SubmissionOutcome> for Verdict {
+    fn from(outcome: &SubmissionOutcome) -> Self {
+        match outcome {
+            SubmissionOutcome::Correct => Verdict::Correct,
+            SubmissionOutcome::Incorrect { hint: None } => Verdict::Incorrect,
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+            } => Verdict::TooHigh,
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooLow),
+            } => Verdict::TooLow,
+            SubmissionOutcome::RateLimited { .. } => Verdict::RateLimited,
+            SubmissionOutcome::AlreadySolved => Verdict::AlreadySolved,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Everything that is known about the answer to one part of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted: Option<String>,
+    /// The largest answer that was rejected as too low.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_low: Option<i64>,
+    /// The smallest answer that was rejected as too high.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_high: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submissions: Vec<Submission>,
+}
+
+/// Reasons for the guard to refuse a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadyAccepted(String),
+    AlreadyRejected,
+    TooHigh(i64),
+    TooLow(i64),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyAccepted(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Rejection::AlreadyRejected => write!(f, "this answer was already rejected."),
+            Rejection::TooHigh(bound) => {
+                write!(f, "answers of {bound} or more are known to be too high.")
+            }
+            Rejection::TooLow(bound) => {
+                write!(f, "answers of {bound} or less are known to be too low.")
+            }
+        }
+    }
+}
+
+/// Submitted answers of one year, keyed by day and part.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(flatten)]
+    days: BTreeMap<String, BTreeMap<String, PartAnswers>>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, a missing file is treated as an empty ledger.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    fn parse(s: &str) -> Result<Self, Error> {
+        toml::from_str(s).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&PartAnswers> {
+        self.days.get(&day.to_string())?.get(&part_key(part))
+    }
+
+    #[must_use]
+    pub fn accepted(&self, day: Day, part: u8) -> Option<&str> {
+        self.get(day, part)?.accepted.as_deref()
+    }
+
+    /// Checks an answer against earlier submissions before it is sent to the server.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        let Some(entry) = self.get(day, part) else {
+            return Ok(());
+        };
+
+        if let Some(accepted) = &entry.accepted {
+            return Err(Rejection::AlreadyAccepted(accepted.clone()));
+        }
+
+        let is_rejected = entry.submissions.iter().any(|s| {
+            s.answer == answer
+                && matches!(
+                    s.verdict,
+                    Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+                )
+        });
+
+        if is_rejected {
+            return Err(Rejection::AlreadyRejected);
+        }
+
+        if let Ok(value) = answer.trim().parse::<i64>() {
+            match (entry.too_high, entry.too_low) {
+                (Some(bound), _) if value >= bound => return Err(Rejection::TooHigh(bound)),
+                (_, Some(bound)) if value <= bound => return Err(Rejection::TooLow(bound)),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the verdict of the server for a submitted answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: &SubmissionOutcome) {
+        let entry = self
+            .days
+            .entry(day.to_string())
+            .or_default()
+            .entry(part_key(part))
+            .or_default();
+
+        let verdict = Verdict::from(outcome);
+        let value = answer.trim().parse::<i64>().ok();
+
+        match (verdict, value) {
+            (Verdict::Correct, _) => entry.accepted = Some(answer.into()),
+            (Verdict::TooHigh, Some(value)) => {
+                entry.too_high = Some(entry.too_high.map_or(value, |b| b.min(value)));
+            }
+            (Verdict::TooLow, Some(value)) => {
+                entry.too_low = Some(entry.too_low.map_or(value, |b| b.max(value)));
+            }
+            _ => {}
+        }
+
+        entry.submissions.push(Submission {
+            answer: answer.into(),
+            verdict,
+        });
+    }
+}
+
+impl Display for Ledger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = toml::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&s)
+    }
+}
+
+fn part_key(part: u8) -> String {
+    format!("part_{part}")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Rejection, Verdict};
+    use crate::day;
+    use crate::template::aoc_client::{Hint, SubmissionOutcome};
+
+    fn incorrect(hint: Hint) -> SubmissionOutcome {
+        SubmissionOutcome::Incorrect { hint: Some(hint) }
+    }
+
+    #[test]
+    fn accepts_unknown_answers() {
+        let ledger = Ledger::default();
+        assert_eq!(ledger.check(day!(1), 1, "42"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            day!(1),
+            1,
+            "abc",
+            &SubmissionOutcome::Incorrect { hint: None },
+        );
+
+        assert_eq!(
+            ledger.check(day!(1), 1, "abc"),
+            Err(Rejection::AlreadyRejected)
+        );
+        assert_eq!(ledger.check(day!(1), 1, "abd"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let mut ledger = Ledger::default();
+        ledger.record(day!(3), 2, "100", &incorrect(Hint::TooHigh));
+        ledger.record(day!(3), 2, "150", &incorrect(Hint::TooHigh));
+        ledger.record(day!(3), 2, "10", &incorrect(Hint::TooLow));
+        ledger.record(day!(3), 2, "5", &incorrect(Hint::TooLow));
+
+        assert_eq!(
+            ledger.check(day!(3), 2, "120"),
+            Err(Rejection::TooHigh(100))
+        );
+        assert_eq!(
+            ledger.check(day!(3), 2, "10"),
+            Err(Rejection::AlreadyRejected)
+        );
+        assert_eq!(ledger.check(day!(3), 2, "7"), Err(Rejection::TooLow(10)));
+        assert_eq!(ledger.check(day!(3), 2, "50"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut ledger = Ledger::default();
+        ledger.record(day!(2), 1, "8", &SubmissionOutcome::Correct);
+
+        assert_eq!(ledger.accepted(day!(2), 1), Some("8"));
+        assert_eq!(
+            ledger.check(day!(2), 1, "9"),
+            Err(Rejection::AlreadyAccepted("8".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_through_toml() {
+        let mut ledger = Ledger::default();
+        ledger.record(day!(5), 1, "10", &incorrect(Hint::TooLow));
+        ledger.record(day!(5), 1, "35", &SubmissionOutcome::Correct);
+
+        let s = ledger.to_string();
+        let parsed = Ledger::parse(&s).unwrap();
+
+        assert_eq!(parsed, ledger);
+        assert_eq!(parsed.get(day!(5), 1).unwrap().submissions.len(), 2);
+        assert_eq!(
+            parsed.get(day!(5), 1).unwrap().submissions[0].verdict,
+            Verdict::TooLow
+        );
+        assert!(s.contains("[05.part_1]"));
+    }
+}
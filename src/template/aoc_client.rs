@@ -0,0 +1,559 @@
+/// Native client for the Advent of Code website.
+/// Authenticates with the session cookie of a logged-in user, the same way `aoc-cli` does.
+use std::{env, fmt::Display, fs, io, path::Path, time::Duration};
+
+use regex::Regex;
+
+use crate::template::{config, paths};
+use crate::{Day, Year};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/Mmarzex/advent-of-code-2023 (advent_of_code template)";
+const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16),
+    Transport(String),
+    UnexpectedResponse,
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or create \"{}\".",
+                config::get().session_file
+            ),
+            AocClientError::BadStatus(400) => {
+                write!(
+                    f,
+                    "the server rejected the request, is the session cookie still valid?"
+                )
+            }
+            AocClientError::BadStatus(404) => {
+                write!(
+                    f,
+                    "the puzzle could not be found, has it been unlocked yet?"
+                )
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+            AocClientError::UnexpectedResponse => {
+                write!(f, "could not make sense of the server response.")
+            }
+            AocClientError::IoError(e) => write!(f, "could not access file system: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The verdict of the server for a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect { hint: Option<Hint> },
+    RateLimited { wait: Duration },
+    AlreadySolved,
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::Incorrect { hint: None } => {
+                write!(f, "That's not the right answer.")
+            }
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+            } => write!(f, "That's not the right answer, your answer is too high."),
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooLow),
+            } => write!(f, "That's not the right answer, your answer is too low."),
+            SubmissionOutcome::RateLimited { wait } => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            SubmissionOutcome::AlreadySolved => write!(f, "This part is already solved."),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: BASE_URL.into(),
+            session: session.trim().into(),
+        }
+    }
+
+    /// Creates a client from the session cookie of the environment.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&session))
+    }
+
+    /// Points the client at a different server, e.g. a local stand-in.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Fetches the personal puzzle input for a day.
+    pub fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(year, day));
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the description of a day and renders it as markdown.
+    pub fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(year, day))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        let html = response.into_string()?;
+
+        let articles = extract_articles(&html);
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse);
+        }
+
+        Ok(articles
+            .iter()
+            .map(|article| html_to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        parse_submission(&response.into_string()?)
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Downloads input and description of a day to the data directory.
+pub fn download(client: &AocClient, year: Year, day: Day) -> Result<(), AocClientError> {
+    let input_path = download_input(client, year, day)?;
+    let puzzle_path = paths::puzzle_path(year, day);
+
+    write_file(&puzzle_path, &client.puzzle(year, day)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Downloads the input of a day to the data directory and returns its path.
+pub fn download_input(client: &AocClient, year: Year, day: Day) -> Result<String, AocClientError> {
+    let input_path = paths::input_path(year, day);
+    write_file(&input_path, &client.input(year, day)?)?;
+    Ok(input_path)
+}
+
+/// Downloads the description of a day to the data directory and returns it.
+pub fn read(client: &AocClient, year: Year, day: Day) -> Result<String, AocClientError> {
+    let puzzle = client.puzzle(year, day)?;
+    write_file(&paths::puzzle_path(year, day), &puzzle)?;
+    Ok(puzzle)
+}
+
+/// Writes a file, creating the year-scoped data directories if needed.
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session);
+    }
+
+    fs::read_to_string(config::get().session_path()?)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+fn parse_submission(html: &str) -> Result<SubmissionOutcome, AocClientError> {
+    let text = extract_articles(html).join(" ");
+
+    if text.contains("That's the right answer") {
+        Ok(SubmissionOutcome::Correct)
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Ok(SubmissionOutcome::Incorrect { hint })
+    } else if text.contains("You gave an answer too recently") {
+        let re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = re.captures(&text).map_or(0, |c| {
+            let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = c[2].parse().unwrap();
+            minutes * 60 + seconds
+        });
+        Ok(SubmissionOutcome::RateLimited {
+            wait: Duration::from_secs(wait),
+        })
+    } else if text.contains("Did you already complete it") {
+        Ok(SubmissionOutcome::AlreadySolved)
+    } else {
+        Err(AocClientError::UnexpectedResponse)
+    }
+}
+
+/// Returns the inner html of all `<article>` elements of a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Renders the small subset of html used in puzzle descriptions as markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            out.push_str(&decode_entities(rest));
+            break;
+        };
+
+        out.push_str(&decode_entities(&rest[..tag_start]));
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        // inside code blocks, only the text content is of interest.
+        if in_pre && name != "pre" {
+            continue;
+        }
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) => out.push('`'),
+            ("em", _) => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("ul", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let needle = format!("{name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// A local stand-in for the Advent of Code server.
+#[cfg(feature = "test_lib")]
+pub(crate) mod stand_in {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Starts a stand-in server that answers the next request with a canned page.
+    /// The received request (head and body) is sent back through the returned channel.
+    pub fn serve(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        serve_all(vec![(status, body)])
+    }
+
+    /// Like [`serve`], but answers one request per canned page, in order.
+    pub fn serve_all(pages: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in pages {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                request.push_str(&String::from_utf8(request_body).unwrap());
+
+                let response = format!(
+                    "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                tx.send(request).unwrap();
+            }
+        });
+
+        (base_url, rx)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{stand_in::serve, AocClient, Hint, SubmissionOutcome};
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn client(base_url: &str) -> AocClient {
+        AocClient::new("secret\n").with_base_url(base_url)
+    }
+
+    #[test]
+    fn downloads_input_with_session() {
+        let (base_url, rx) = serve(200, "1abc2\npqr3stu8vwx\n");
+        let input = client(&base_url).input(year!(2023), day!(1)).unwrap();
+        let request = rx.recv().unwrap();
+
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn renders_puzzle_as_markdown() {
+        let (base_url, _rx) = serve(
+            200,
+            "<main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>\
+             <p>Consider <a href=\"/2023/about\">this</a> &amp; that:</p>\
+             <pre><code>1abc2\n<em>pqr3stu8vwx</em>\n</code></pre>\
+             <p>Adding these together produces <code><em>142</em></code>.</p></article>\
+             <p>To play, please identify yourself.</p></main>",
+        );
+        let puzzle = client(&base_url).puzzle(year!(2023), day!(1)).unwrap();
+
+        assert_eq!(
+            puzzle,
+            [
+                "## --- Day 1: Trebuchet?! ---",
+                "",
+                "Consider [this](/2023/about) & that:",
+                "",
+                "```",
+                "1abc2",
+                "pqr3stu8vwx",
+                "```",
+                "",
+                "Adding these together produces `*142*`.",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, rx) = serve(
+            200,
+            "<article><p>That's the right answer!  You are one gold star closer.</p></article>",
+        );
+        let outcome = client(&base_url)
+            .submit(year!(2023), day!(7), 2, "42")
+            .unwrap();
+        let request = rx.recv().unwrap();
+
+        assert_eq!(outcome, SubmissionOutcome::Correct);
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_incorrect_answers() {
+        let (base_url, _rx) = serve(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        );
+        let outcome = client(&base_url)
+            .submit(year!(2023), day!(1), 1, "100")
+            .unwrap();
+        assert_eq!(
+            outcome,
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooHigh)
+            }
+        );
+
+        let (base_url, _rx) = serve(
+            200,
+            "<article><p>That's not the right answer.  If you're stuck, ...</p></article>",
+        );
+        let outcome = client(&base_url)
+            .submit(year!(2023), day!(1), 1, "100")
+            .unwrap();
+        assert_eq!(outcome, SubmissionOutcome::Incorrect { hint: None });
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let (base_url, _rx) = serve(
+            200,
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>",
+        );
+        let outcome = client(&base_url)
+            .submit(year!(2023), day!(1), 1, "100")
+            .unwrap();
+        assert_eq!(
+            outcome,
+            SubmissionOutcome::RateLimited {
+                wait: Duration::from_secs(65)
+            }
+        );
+    }
+
+    #[test]
+    fn parses_already_solved() {
+        let (base_url, _rx) = serve(
+            200,
+            "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        );
+        let outcome = client(&base_url)
+            .submit(year!(2023), day!(1), 1, "100")
+            .unwrap();
+        assert_eq!(outcome, SubmissionOutcome::AlreadySolved);
+    }
+
+    #[test]
+    fn errors_on_bad_status() {
+        let (base_url, _rx) = serve(404, "Not Found");
+        assert!(client(&base_url).input(year!(2023), day!(25)).is_err());
+    }
+}
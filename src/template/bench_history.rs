@@ -0,0 +1,392 @@
+/// Module that keeps a history of benchmark runs to detect performance regressions.
+/// Every run is appended as one JSON line to the history file.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::protocol::{PartRecord, PartStatus};
+
+pub const MACHINE_ENV: &str = "AOC_MACHINE";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark history: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// The results of one benchmark run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub revision: Option<String>,
+    pub machine: String,
+    pub records: Vec<PartRecord>,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for the current time, git revision and machine.
+    #[must_use]
+    pub fn new(records: Vec<PartRecord>) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            revision: get_revision(),
+            machine: get_machine(),
+            records,
+        }
+    }
+
+    fn find(&self, year: u16, day: u8, part: u8) -> Option<&PartRecord> {
+        self.records
+            .iter()
+            .find(|r| r.year == year && r.day == day && r.part == part)
+    }
+
+    /// The years of all benchmarked parts, in ascending order.
+    fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> = self.records.iter().map(|r| r.year).collect();
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+
+    /// The number of parts that were benchmarked in both runs.
+    #[must_use]
+    pub fn shared_parts(&self, other: &HistoryEntry) -> usize {
+        self.records
+            .iter()
+            .filter(|r| other.find(r.year, r.day, r.part).is_some())
+            .count()
+    }
+}
+
+pub fn append(path: impl AsRef<Path>, entry: &HistoryEntry) -> Result<(), Error> {
+    let line = serde_json::to_string(entry).map_err(|e| Error::Parser(e.to_string()))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Reads all entries of the history, oldest first. A missing file is treated as an empty history.
+/// Lines that can not be parsed are skipped with a warning, so one bad line does not hide the rest of the history.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<HistoryEntry>, Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => Ok(parse(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse(s: &str) -> Vec<HistoryEntry> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .filter_map(|(i, l)| match serde_json::from_str(l) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("Skipped line {} of benchmark history: {e}", i + 1);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Finds the latest entry of a machine and the baseline to compare it with.
+/// The baseline is the latest older entry whose revision starts with `revision`, or of any revision if none is given.
+/// Entries that benchmarked the same years as the latest entry are preferred, e.g. over a run of a single year.
+#[must_use]
+pub fn select<'a>(
+    history: &'a [HistoryEntry],
+    machine: &str,
+    revision: Option<&str>,
+) -> Option<(&'a HistoryEntry, &'a HistoryEntry)> {
+    let mut entries = history.iter().rev().filter(|e| e.machine == machine);
+    let current = entries.next()?;
+
+    let candidates: Vec<&HistoryEntry> = entries
+        .filter(|e| {
+            revision.is_none_or(|revision| {
+                e.revision
+                    .as_deref()
+                    .is_some_and(|r| r.starts_with(revision))
+            })
+        })
+        .collect();
+
+    let years = current.years();
+    let baseline = candidates
+        .iter()
+        .find(|e| e.years() == years)
+        .or_else(|| candidates.first())?;
+
+    Some((baseline, current))
+}
+
+/// The change in median time and status of one part between two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub baseline_status: PartStatus,
+    pub current_status: PartStatus,
+    pub baseline_nanos: u128,
+    pub current_nanos: u128,
+    /// The change in median time, only if the part was solved in both runs.
+    pub percent: Option<f64>,
+    pub is_regression: bool,
+}
+
+/// Compares all parts that are in both runs.
+/// Parts that became slower by more than `threshold` percent are flagged as regressions,
+/// as are parts whose status changed to anything but solved, e.g. from solved to timeout.
+#[must_use]
+pub fn compare(baseline: &HistoryEntry, current: &HistoryEntry, threshold: f64) -> Vec<Delta> {
+    let mut deltas: Vec<Delta> = current
+        .records
+        .iter()
+        .filter_map(|r| {
+            let before = baseline.find(r.year, r.day, r.part)?;
+            let after = current.find(r.year, r.day, r.part)?;
+
+            let baseline_nanos = before.timing.median.as_nanos();
+            let current_nanos = after.timing.median.as_nanos();
+
+            let is_solved =
+                before.status == PartStatus::Solved && after.status == PartStatus::Solved;
+
+            #[allow(clippy::cast_precision_loss)]
+            let percent = match (is_solved, baseline_nanos) {
+                (false, _) => None,
+                (true, 0) => Some(0.0),
+                (true, _) => Some(
+                    (current_nanos as f64 - baseline_nanos as f64) / baseline_nanos as f64 * 100.0,
+                ),
+            };
+
+            // parts that were not solved before and still are not are left out, there is nothing to compare.
+            if percent.is_none() && before.status == after.status {
+                return None;
+            }
+
+            Some(Delta {
+                year: r.year,
+                day: r.day,
+                part: r.part,
+                baseline_status: before.status,
+                current_status: after.status,
+                baseline_nanos,
+                current_nanos,
+                percent,
+                is_regression: match percent {
+                    Some(percent) => percent > threshold,
+                    None => after.status != PartStatus::Solved,
+                },
+            })
+        })
+        .collect();
+
+    deltas.sort_by_key(|d| (d.year, d.day, d.part));
+    deltas.dedup_by_key(|d| (d.year, d.day, d.part));
+    deltas
+}
+
+fn get_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The label of the current machine, taken from `AOC_MACHINE` or the host name.
+#[must_use]
+pub fn get_machine() -> String {
+    [MACHINE_ENV, "HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| env::var(key).ok().filter(|v| !v.is_empty()))
+        .or_else(|| {
+            fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|s| s.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, parse, select, HistoryEntry};
+    use crate::template::{
+        protocol::{PartRecord, PartStatus},
+        runner::Stats,
+    };
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn entry_of_year(
+        timestamp: u64,
+        revision: &str,
+        year: crate::Year,
+        nanos: [u64; 2],
+    ) -> HistoryEntry {
+        let mut entry = entry(timestamp, revision, "laptop", nanos);
+        for record in &mut entry.records {
+            record.year = year.into_inner();
+        }
+        entry
+    }
+
+    fn entry(timestamp: u64, revision: &str, machine: &str, nanos: [u64; 2]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            revision: Some(revision.into()),
+            machine: machine.into(),
+            records: vec![
+                PartRecord::new(
+                    year!(2023),
+                    day!(1),
+                    1,
+                    Some("1".into()),
+                    Stats::single(Duration::from_nanos(nanos[0])),
+                ),
+                PartRecord::new(
+                    year!(2023),
+                    day!(1),
+                    2,
+                    Some("2".into()),
+                    Stats::single(Duration::from_nanos(nanos[1])),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn selects_previous_entry_of_same_machine() {
+        let history = vec![
+            entry(1, "aaa1111", "laptop", [100, 100]),
+            entry(2, "bbb2222", "laptop", [100, 100]),
+            entry(3, "ccc3333", "ci", [100, 100]),
+            entry(4, "ddd4444", "laptop", [100, 100]),
+        ];
+
+        let (baseline, current) = select(&history, "laptop", None).unwrap();
+        assert_eq!(baseline.timestamp, 2);
+        assert_eq!(current.timestamp, 4);
+
+        let (baseline, _) = select(&history, "laptop", Some("aaa")).unwrap();
+        assert_eq!(baseline.timestamp, 1);
+
+        assert!(select(&history, "laptop", Some("ccc")).is_none());
+        assert!(select(&history, "ci", None).is_none());
+    }
+
+    #[test]
+    fn prefers_baseline_of_same_years() {
+        let history = vec![
+            entry_of_year(1, "aaa1111", year!(2023), [100, 100]),
+            entry_of_year(2, "bbb2222", year!(2022), [100, 100]),
+            entry_of_year(3, "ccc3333", year!(2023), [100, 100]),
+        ];
+
+        let (baseline, current) = select(&history, "laptop", None).unwrap();
+        assert_eq!(baseline.timestamp, 1);
+        assert_eq!(baseline.shared_parts(current), 2);
+
+        // without a run of the same years, the latest run is the baseline, even if no part is shared.
+        let (baseline, current) = select(&history[1..], "laptop", None).unwrap();
+        assert_eq!(baseline.timestamp, 2);
+        assert_eq!(baseline.shared_parts(current), 0);
+        assert!(compare(baseline, current, 10.0).is_empty());
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let baseline = entry(1, "aaa1111", "laptop", [100, 1000]);
+        let current = entry(2, "bbb2222", "laptop", [105, 1500]);
+
+        let deltas = compare(&baseline, &current, 10.0);
+
+        assert_eq!(deltas.len(), 2);
+        assert!((deltas[0].percent.unwrap() - 5.0).abs() < 1e-9);
+        assert!(!deltas[0].is_regression);
+        assert!((deltas[1].percent.unwrap() - 50.0).abs() < 1e-9);
+        assert!(deltas[1].is_regression);
+    }
+
+    #[test]
+    fn flags_parts_that_stopped_solving() {
+        let baseline = entry(1, "aaa1111", "laptop", [100, 1000]);
+        let mut current = entry(2, "bbb2222", "laptop", [100, 1000]);
+        current.records[1] = PartRecord::timeout(year!(2023), day!(1), 2, Duration::from_secs(10));
+
+        let deltas = compare(&baseline, &current, 10.0);
+
+        assert_eq!(deltas.len(), 2);
+        assert!(!deltas[0].is_regression);
+        assert_eq!(deltas[1].baseline_status, PartStatus::Solved);
+        assert_eq!(deltas[1].current_status, PartStatus::Timeout);
+        assert_eq!(deltas[1].percent, None);
+        assert!(deltas[1].is_regression);
+
+        // a part that solves again is not a regression.
+        let deltas = compare(&current, &baseline, 10.0);
+        assert_eq!(deltas[1].current_status, PartStatus::Solved);
+        assert!(!deltas[1].is_regression);
+    }
+
+    #[test]
+    fn roundtrips_through_json_lines() {
+        let entries = [
+            entry(1, "aaa1111", "laptop", [100, 1000]),
+            entry(2, "bbb2222", "laptop", [105, 1500]),
+        ];
+        let s = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect::<String>();
+
+        assert_eq!(parse(&s), entries);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let entry = entry(1, "aaa1111", "laptop", [100, 1000]);
+        let s = format!(
+            "{}\n{{\"timestamp\": 2\n{}\n",
+            serde_json::to_string(&entry).unwrap(),
+            serde_json::to_string(&entry).unwrap()
+        );
+
+        assert_eq!(parse(&s), vec![entry.clone(), entry]);
+    }
+}
@@ -0,0 +1,727 @@
+/// Description of the commands of the `advent_of_code` binary.
+/// Help texts, the validation of flags and shell completions are all generated from [`COMMANDS`].
+use std::{fmt::Display, str::FromStr};
+
+use crate::all_days;
+
+/// Exit code of invalid invocations, commands that fail exit with `1`.
+pub const USAGE_EXIT_CODE: i32 = 2;
+
+const BIN_NAME: &str = "advent_of_code";
+
+pub struct Flag {
+    pub name: &'static str,
+    /// The name of the value of the flag, [`None`] for switches.
+    pub value: Option<&'static str>,
+    /// Values that are suggested by completions.
+    pub choices: &'static [&'static str],
+    pub help: &'static str,
+}
+
+pub struct Positional {
+    pub name: &'static str,
+    pub choices: Choices,
+}
+
+#[derive(Clone, Copy)]
+pub enum Choices {
+    Days,
+    Values(&'static [&'static str]),
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub positionals: &'static [Positional],
+    pub flags: &'static [Flag],
+}
+
+const fn switch(name: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: None,
+        choices: &[],
+        help,
+    }
+}
+
+const fn option(name: &'static str, value: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: Some(value),
+        choices: &[],
+        help,
+    }
+}
+
+const fn choice(
+    name: &'static str,
+    value: &'static str,
+    choices: &'static [&'static str],
+    help: &'static str,
+) -> Flag {
+    Flag {
+        name,
+        value: Some(value),
+        choices,
+        help,
+    }
+}
+
+const DAY: Positional = Positional {
+    name: "day",
+    choices: Choices::Days,
+};
+
+const YEAR: Flag = option(
+    "--year",
+    "year",
+    "the year of the puzzle, defaults to the configured year",
+);
+const YEARS: Flag = option("--year", "year", "only this year, defaults to all years");
+const TIMEOUT: Flag = option("--timeout", "seconds", "the time budget of each part");
+const TEMPLATE: Flag = choice(
+    "--template",
+    "name",
+    &["default", "parsed-struct"],
+    "the template of the module",
+);
+const TYPE: Flag = choice(
+    "--type",
+    "type",
+    &["u32", "u64", "usize", "i32", "i64", "isize", "String"],
+    "the return type of the parts, defaults to `u32`",
+);
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Creates the module, input and examples files of a day",
+        positionals: &[DAY],
+        flags: &[YEAR, TEMPLATE, TYPE],
+    },
+    Command {
+        name: "start",
+        about: "Scaffolds a day, downloads it, extracts its examples and shows the puzzle",
+        positionals: &[DAY],
+        flags: &[
+            YEAR,
+            switch("--wait", "count down to the unlock of the puzzle first"),
+            TEMPLATE,
+            TYPE,
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Downloads the input and description of a day",
+        positionals: &[DAY],
+        flags: &[YEAR],
+    },
+    Command {
+        name: "read",
+        about: "Downloads the description of a day and shows it",
+        positionals: &[DAY],
+        flags: &[YEAR],
+    },
+    Command {
+        name: "examples",
+        about: "Lists the code blocks of a saved description or adds them as examples",
+        positionals: &[DAY],
+        flags: &[
+            YEAR,
+            option(
+                "--pick",
+                "blocks",
+                "add these blocks as examples, e.g. `1,3`",
+            ),
+        ],
+    },
+    Command {
+        name: "solve",
+        about: "Runs the solution of a day",
+        positionals: &[DAY],
+        flags: &[
+            YEAR,
+            switch("--release", "compile with optimizations"),
+            switch("--time", "benchmark the parts"),
+            choice(
+                "--submit",
+                "part",
+                &["1", "2"],
+                "submit the answer of a part",
+            ),
+            switch("--watch", "run again when the solution or its data change"),
+            TIMEOUT,
+            option("--input", "path", "run against another input file"),
+            switch("--stdin", "run against the standard input"),
+            switch("--example", "run against the example of the day"),
+        ],
+    },
+    Command {
+        name: "submit",
+        about: "Lists the answers that wait for a rate limit, or submits them",
+        positionals: &[],
+        flags: &[switch(
+            "--flush",
+            "submit the queued answers once their cooldown has passed",
+        )],
+    },
+    Command {
+        name: "all",
+        about: "Runs the solutions of all days",
+        positionals: &[],
+        flags: &[
+            YEARS,
+            switch("--release", "compile with optimizations"),
+            switch("--time", "benchmark the parts and update the readme"),
+            switch("--json", "print the records of all parts as JSON"),
+            option("--jobs", "n", "run up to n days at once"),
+            TIMEOUT,
+            choice(
+                "--columns",
+                "list",
+                &["parse", "total", "share", "delta"],
+                "optional columns of the benchmark table",
+            ),
+            choice(
+                "--sort",
+                "order",
+                &["day", "cost"],
+                "the order of the benchmark table",
+            ),
+            switch("--chart", "write a bar chart of the benchmarks"),
+        ],
+    },
+    Command {
+        name: "verify",
+        about: "Compares the answers of all solutions with the accepted answers",
+        positionals: &[],
+        flags: &[YEARS],
+    },
+    Command {
+        name: "stars",
+        about: "Fills the readme stars table with the accepted answers",
+        positionals: &[],
+        flags: &[],
+    },
+    Command {
+        name: "report",
+        about: "Writes an HTML report of progress and benchmarks",
+        positionals: &[],
+        flags: &[
+            YEARS,
+            switch("--redact", "hide the answers"),
+            option("--output", "path", "where to write the report"),
+        ],
+    },
+    Command {
+        name: "bench",
+        about: "Compares the latest benchmark run with a baseline",
+        positionals: &[Positional {
+            name: "mode",
+            choices: Choices::Values(&["compare"]),
+        }],
+        flags: &[
+            option(
+                "--baseline",
+                "revision",
+                "compare with a run of this revision",
+            ),
+            option(
+                "--threshold",
+                "percent",
+                "fail if a part got slower by this much",
+            ),
+        ],
+    },
+    Command {
+        name: "completions",
+        about: "Prints a completion script for a shell",
+        positionals: &[Positional {
+            name: "shell",
+            choices: Choices::Values(&["bash", "zsh", "fish"]),
+        }],
+        flags: &[],
+    },
+];
+
+const HELP_FLAG: Flag = switch("--help", "print help");
+
+/// Overrides a setting of the [config](crate::template::config) for one run.
+pub const SET_FLAG: &str = "--set";
+
+/// Flags that every command accepts.
+const GLOBAL_FLAGS: &[Flag] = &[
+    option(SET_FLAG, "key=value", "override a setting of `aoc.toml`"),
+    HELP_FLAG,
+];
+
+#[must_use]
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+impl Flag {
+    fn usage(&self) -> String {
+        match self.value {
+            Some(value) => format!("{} <{value}>", self.name),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+impl Command {
+    #[must_use]
+    pub fn usage(&self) -> String {
+        let mut usage = format!("Usage: {BIN_NAME} {}", self.name);
+        for positional in self.positionals {
+            usage += &format!(" <{}>", positional.name);
+        }
+        if !self.flags.is_empty() {
+            usage += " [options]";
+        }
+        usage
+    }
+
+    /// The help of the command with all of its flags.
+    #[must_use]
+    pub fn help(&self) -> String {
+        let mut lines = vec![self.about.to_string(), String::new(), self.usage()];
+
+        lines.push(String::new());
+        lines.push("Options:".into());
+        let width = self
+            .flags
+            .iter()
+            .chain(GLOBAL_FLAGS)
+            .map(|f| f.usage().len())
+            .max()
+            .unwrap_or_default();
+        for flag in self.flags.iter().chain(GLOBAL_FLAGS) {
+            lines.push(format!("  {:width$}  {}", flag.usage(), flag.help));
+        }
+
+        lines.join("\n")
+    }
+
+    fn flag(&self, arg: &str) -> Option<&Flag> {
+        let name = arg.split_once('=').map_or(arg, |(name, _)| name);
+        self.flags
+            .iter()
+            .chain(GLOBAL_FLAGS)
+            .find(|f| f.name == name)
+    }
+
+    /// Checks that every flag of `args` is a flag of the command.
+    pub fn check_flags(&self, args: &[String]) -> Result<(), String> {
+        match args
+            .iter()
+            .filter(|arg| arg.starts_with("--"))
+            .find(|arg| self.flag(arg).is_none())
+        {
+            Some(arg) => Err(format!(
+                "unknown option `{arg}` for `{}`\n\n{}",
+                self.name,
+                self.usage()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Puts the `defaults` of the config in front of `args`, except for flags that `args` gives itself.
+    pub fn with_defaults(
+        &self,
+        defaults: &[String],
+        args: &[String],
+    ) -> Result<Vec<String>, String> {
+        let is_given = |flag: &Flag| {
+            args.iter()
+                .any(|arg| self.flag(arg).is_some_and(|f| f.name == flag.name))
+        };
+
+        let mut merged = vec![];
+        let mut defaults = defaults.iter();
+
+        while let Some(arg) = defaults.next() {
+            let name = arg.split_once('=').map_or(arg.as_str(), |(name, _)| name);
+            let Some(flag) = self.flags.iter().find(|f| f.name == name) else {
+                return Err(format!(
+                    "invalid default `{arg}` of `{}`, expecting one of its options",
+                    self.name
+                ));
+            };
+
+            let mut group = vec![arg.clone()];
+            if flag.value.is_some() && !arg.contains('=') {
+                match defaults.next() {
+                    Some(value) => group.push(value.clone()),
+                    None => {
+                        return Err(format!(
+                            "default `{arg}` of `{}` is missing its value",
+                            self.name
+                        ))
+                    }
+                }
+            }
+
+            if !is_given(flag) {
+                merged.extend(group);
+            }
+        }
+
+        merged.extend(args.iter().cloned());
+        Ok(merged)
+    }
+}
+
+/// The overview of all commands.
+#[must_use]
+pub fn help() -> String {
+    let mut lines = vec![
+        "Solutions and tools for Advent of Code.".to_string(),
+        String::new(),
+        format!("Usage: {BIN_NAME} <command> [options]"),
+        String::new(),
+        "Commands:".into(),
+    ];
+
+    let width = COMMANDS
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or_default();
+    for command in COMMANDS {
+        lines.push(format!("  {:width$}  {}", command.name, command.about));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "Run `{BIN_NAME} <command> --help` for the options of a command."
+    ));
+    lines.join("\n")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            s => Err(format!(
+                "unknown shell `{s}`, expected `bash`, `zsh` or `fish`"
+            )),
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shell::Bash => write!(f, "bash"),
+            Shell::Zsh => write!(f, "zsh"),
+            Shell::Fish => write!(f, "fish"),
+        }
+    }
+}
+
+impl Choices {
+    fn values(self) -> Vec<String> {
+        match self {
+            Choices::Days => all_days().map(|d| d.into_inner().to_string()).collect(),
+            Choices::Values(values) => values.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+fn command_names() -> String {
+    COMMANDS
+        .iter()
+        .map(|c| c.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Prints a completion script that completes commands, their flags, flag values and day numbers.
+#[must_use]
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    }
+}
+
+fn bash() -> String {
+    let name = BIN_NAME;
+    let mut out = vec![
+        format!("_{name}() {{"),
+        "    local cur prev command".into(),
+        "    cur=\"${COMP_WORDS[COMP_CWORD]}\"".into(),
+        "    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"".into(),
+        "    command=\"${COMP_WORDS[1]}\"".into(),
+        String::new(),
+        "    if [ \"$COMP_CWORD\" -eq 1 ]; then".into(),
+        format!(
+            "        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            command_names()
+        ),
+        "        return".into(),
+        "    fi".into(),
+        String::new(),
+        "    case \"$command\" in".into(),
+    ];
+
+    for command in COMMANDS {
+        out.push(format!("        {})", command.name));
+        out.push("            case \"$prev\" in".into());
+        for flag in command.flags {
+            let reply = match flag.value {
+                None => continue,
+                Some(_) if !flag.choices.is_empty() => format!(
+                    "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                    flag.choices.join(" ")
+                ),
+                Some("path") => "COMPREPLY=($(compgen -f -- \"$cur\"))".into(),
+                Some(_) => "COMPREPLY=()".into(),
+            };
+            out.push(format!("                {}) {reply}; return ;;", flag.name));
+        }
+        out.push("            esac".into());
+
+        let flags = command
+            .flags
+            .iter()
+            .chain(GLOBAL_FLAGS)
+            .map(|f| f.name)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let values = command
+            .positionals
+            .iter()
+            .flat_map(|p| p.choices.values())
+            .collect::<Vec<_>>()
+            .join(" ");
+        out.push(format!(
+            "            COMPREPLY=($(compgen -W \"{flags} {values}\" -- \"$cur\"))"
+        ));
+        out.push("            ;;".into());
+    }
+
+    out.push("    esac".into());
+    out.push("}".into());
+    out.push(format!("complete -F _{name} {name}"));
+    out.push(String::new());
+    out.join("\n")
+}
+
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh() -> String {
+    let name = BIN_NAME;
+    let mut out = vec![
+        format!("#compdef {name}"),
+        String::new(),
+        format!("_{name}() {{"),
+        "    local -a commands".into(),
+        "    commands=(".into(),
+    ];
+
+    for command in COMMANDS {
+        out.push(format!(
+            "        '{}:{}'",
+            command.name,
+            zsh_escape(command.about)
+        ));
+    }
+    out.push("    )".into());
+    out.push(String::new());
+    out.push("    if (( CURRENT == 2 )); then".into());
+    out.push("        _describe 'command' commands".into());
+    out.push("        return".into());
+    out.push("    fi".into());
+    out.push(String::new());
+    out.push("    case \"$words[2]\" in".into());
+
+    for command in COMMANDS {
+        out.push(format!("        {})", command.name));
+        let mut specs = vec!["'1: :->command'".to_string()];
+        for (i, positional) in command.positionals.iter().enumerate() {
+            specs.push(format!(
+                "'{}:{}:({})'",
+                i + 2,
+                positional.name,
+                positional.choices.values().join(" ")
+            ));
+        }
+        for flag in command.flags.iter().chain(GLOBAL_FLAGS) {
+            let action = match flag.value {
+                Some(value) if !flag.choices.is_empty() => {
+                    format!(":{value}:({})", flag.choices.join(" "))
+                }
+                Some("path") => ":path:_files".into(),
+                Some(value) => format!(":{value}: "),
+                None => String::new(),
+            };
+            specs.push(format!(
+                "'{}[{}]{action}'",
+                flag.name,
+                zsh_escape(flag.help)
+            ));
+        }
+        out.push(format!("            _arguments {}", specs.join(" ")));
+        out.push("            ;;".into());
+    }
+
+    out.push("    esac".into());
+    out.push("}".into());
+    out.push(String::new());
+    out.push(format!("compdef _{name} {name}"));
+    out.push(String::new());
+    out.join("\n")
+}
+
+fn fish() -> String {
+    let name = BIN_NAME;
+    let mut out = vec![
+        format!("complete -c {name} -f"),
+        format!(
+            "complete -c {name} -n '__fish_use_subcommand' -l help -d '{}'",
+            HELP_FLAG.help
+        ),
+    ];
+
+    for command in COMMANDS {
+        out.push(format!(
+            "complete -c {name} -n '__fish_use_subcommand' -a {} -d '{}'",
+            command.name,
+            command.about.replace('\'', "\\'")
+        ));
+
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+        for positional in command.positionals {
+            out.push(format!(
+                "complete -c {name} -n '{condition}' -a '{}'",
+                positional.choices.values().join(" ")
+            ));
+        }
+        for flag in command.flags.iter().chain(GLOBAL_FLAGS) {
+            let mut line = format!(
+                "complete -c {name} -n '{condition}' -l {}",
+                flag.name.trim_start_matches("--")
+            );
+            match flag.value {
+                Some(_) if !flag.choices.is_empty() => {
+                    line += &format!(" -x -a '{}'", flag.choices.join(" "));
+                }
+                Some("path") => line += " -r -F",
+                Some(_) => line += " -x",
+                None => {}
+            }
+            line += &format!(" -d '{}'", flag.help.replace('\'', "\\'"));
+            out.push(line);
+        }
+    }
+
+    out.push(String::new());
+    out.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{completions, find, help, Shell, COMMANDS};
+
+    #[test]
+    fn describes_every_command() {
+        let help = help();
+        for command in COMMANDS {
+            assert!(help.contains(command.name));
+            assert!(command.help().contains(&command.usage()));
+        }
+        assert_eq!(
+            find("solve").unwrap().usage(),
+            "Usage: advent_of_code solve <day> [options]"
+        );
+        assert!(find("solve")
+            .unwrap()
+            .help()
+            .contains("  --submit <part>      submit the answer of a part"));
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        let solve = find("solve").unwrap();
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert!(solve
+            .check_flags(&args(&["1", "--release", "--timeout=2"]))
+            .is_ok());
+        let e = solve.check_flags(&args(&["1", "--relase"])).unwrap_err();
+        assert!(e.starts_with("unknown option `--relase` for `solve`"));
+        assert!(solve
+            .check_flags(&args(&["1", "--set", "year=2022"]))
+            .is_ok());
+    }
+
+    #[test]
+    fn merges_default_flags() {
+        let solve = find("solve").unwrap();
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let defaults = args(&["--release", "--timeout", "10", "--submit=1"]);
+
+        assert_eq!(
+            solve.with_defaults(&defaults, &args(&["1"])).unwrap(),
+            args(&["--release", "--timeout", "10", "--submit=1", "1"])
+        );
+        assert_eq!(
+            solve
+                .with_defaults(&defaults, &args(&["1", "--timeout=2", "--submit", "2"]))
+                .unwrap(),
+            args(&["--release", "1", "--timeout=2", "--submit", "2"])
+        );
+
+        assert!(solve.with_defaults(&args(&["1"]), &[]).is_err());
+        assert!(solve.with_defaults(&args(&["--jobs", "2"]), &[]).is_err());
+        assert!(solve.with_defaults(&args(&["--timeout"]), &[]).is_err());
+    }
+
+    #[test]
+    fn completes_commands_flags_and_days() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completions(shell);
+            assert!(script.contains("scaffold"), "{shell}");
+            assert!(
+                script.contains("--release") || script.contains("-l release"),
+                "{shell}"
+            );
+            assert!(script.contains("1 2 3"), "{shell}");
+            assert!(script.contains("24 25"), "{shell}");
+        }
+
+        assert!(completions(Shell::Bash)
+            .contains("--sort) COMPREPLY=($(compgen -W \"day cost\" -- \"$cur\")); return ;;"));
+        assert!(completions(Shell::Zsh).starts_with("#compdef advent_of_code\n"));
+        assert!(completions(Shell::Fish).contains(
+            "complete -c advent_of_code -n '__fish_seen_subcommand_from completions' -a 'bash zsh fish'"
+        ));
+    }
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("zsh".parse::<Shell>(), Ok(Shell::Zsh));
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+}
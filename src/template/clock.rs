@@ -0,0 +1,129 @@
+/// Time source of countdowns, e.g. to the unlock of a puzzle or the end of a rate limit.
+/// Countdowns take a [`Clock`], so they can be tested without waiting.
+use std::{
+    io::{self, Write},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Counts down to `until` on `out` once per second, e.g. `⏳ Unlocks in 00:00:03` for the `label` "Unlocks in".
+/// Returns right away if `until` has passed, the countdown is left on the current line.
+pub fn countdown(
+    clock: &mut impl Clock,
+    until: SystemTime,
+    label: &str,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    while let Ok(remaining) = until.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+
+        // round up, so the countdown ends at 00:00:01 instead of showing zero while waiting.
+        let shown =
+            Duration::from_secs(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0));
+        write!(out, "\r⏳ {label} {}", format_remaining(shown))?;
+        out.flush()?;
+
+        let tick = remaining - Duration::from_secs(shown.as_secs().saturating_sub(1));
+        clock.sleep(tick);
+    }
+
+    Ok(())
+}
+
+/// A clock that only advances when it is slept on.
+#[cfg(feature = "test_lib")]
+pub(crate) struct FakeClock {
+    pub now: SystemTime,
+    pub sleeps: Vec<Duration>,
+}
+
+#[cfg(feature = "test_lib")]
+impl FakeClock {
+    pub fn at(now: SystemTime) -> Self {
+        Self {
+            now,
+            sleeps: vec![],
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        self.now
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.now += duration;
+        self.sleeps.push(duration);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{countdown, FakeClock};
+
+    #[test]
+    fn counts_down_once_per_second() {
+        let until = UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut clock = FakeClock::at(until - Duration::from_millis(2_500));
+        let mut out = vec![];
+
+        countdown(&mut clock, until, "Unlocks in", &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(clock.now, until);
+        assert_eq!(
+            clock.sleeps,
+            vec![
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(1)
+            ]
+        );
+        assert!(out.contains("Unlocks in 00:00:03"));
+        assert!(out.ends_with("Unlocks in 00:00:01"));
+    }
+
+    #[test]
+    fn does_not_wait_for_the_past() {
+        let until = UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut clock = FakeClock::at(until + Duration::from_secs(3_600));
+        let mut out = vec![];
+
+        countdown(&mut clock, until, "Unlocks in", &mut out).unwrap();
+        assert!(clock.sleeps.is_empty());
+        assert!(out.is_empty());
+    }
+}
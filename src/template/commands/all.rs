@@ -1,40 +1,145 @@
-use std::io;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    alloc,
+    bench_history::{self, HistoryEntry},
+    commands::{solve, stars},
+    input::InputSource,
+    paths,
+    protocol::{self, PartRecord, PartStatus},
+    readme_benchmarks::{self, TableOptions, Timings},
+    registry::{Entry, Registry},
+    runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::{all_days, Day, Year};
+
+/// Runs all days of a year, or of all registered years if no year is given.
+/// Up to `jobs` days run concurrently, timed runs are always serial to keep benchmarks valid.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    is_release: bool,
+    is_timed: bool,
+    is_json: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    table: &TableOptions,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let mut records: Vec<PartRecord> = vec![];
+
+    // solutions print to the stdout of this process, which is kept for the records in JSON mode.
+    let mut json_out = is_json.then(|| {
+        protocol::take_stdout().unwrap_or_else(|e| {
+            eprintln!("Failed to move solution output to stderr: {e}");
+            Box::new(io::stdout())
+        })
+    });
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+    let years = year.map_or_else(|| registry.years(), |year| vec![year]);
+    let days: Vec<(Year, Day)> = years
+        .iter()
+        .flat_map(|&year| all_days().map(move |day| (year, day)))
+        .collect();
+
+    let jobs = if is_timed && jobs > 1 {
+        eprintln!("Timed runs are serial, ignoring `--jobs {jobs}`.");
+        1
+    } else {
+        jobs
+    };
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    // concurrent days run in their own process, so the lines their solutions print stay grouped by day.
+    // days with a time budget do as well, a part that timed out keeps running until its process exits.
+    let is_isolated = |entry: &Entry| jobs > 1 || timeout.or(entry.timeout).is_some();
+    let isolated: Vec<String> = days
+        .iter()
+        .filter_map(|&(year, day)| registry.get(year, day))
+        .filter(|entry| is_isolated(entry))
+        .map(|entry| paths::bin_name(entry.year, entry.day))
+        .collect();
+    let executables = if isolated.is_empty() {
+        HashMap::new()
+    } else {
+        build_solutions(&isolated, is_release)
+    };
+
+    run_ordered(
+        &days,
+        jobs,
+        |&(year, day)| {
+            let entry = registry.get(year, day)?;
+            Some(if is_isolated(entry) {
+                let executable = executables
+                    .get(&paths::bin_name(year, day))
+                    .map(PathBuf::as_path);
+                run_isolated(executable, year, day, is_timed, timeout)
+            } else {
+                entry
+                    .run(is_timed, timeout)
+                    .map(DayOutput::from)
+                    .map_err(|e| e.to_string())
+            })
+        },
+        |&(year, day), output| {
+            if !is_json && day == 1 {
+                if year != years[0] {
+                    println!();
+                }
+                println!("{ANSI_BOLD}{ANSI_ITALIC}Year {year}{ANSI_RESET}");
+            }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            print_day(year, day, output, is_json, &mut timings, &mut records);
+        },
+    );
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    if let Some(out) = &mut json_out {
+        let json = serde_json::to_string_pretty(&records).unwrap();
+        if let Err(e) = writeln!(out, "{json}").and_then(|()| out.flush()) {
+            eprintln!("Failed to write records: {e}");
         }
-    });
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if !is_json {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+            match bench_history::append(paths::history_path(), &HistoryEntry::new(records)) {
+                Ok(()) => eprintln!("Successfully recorded benchmarks in history."),
+                Err(e) => eprintln!("Failed to record benchmarks in history: {e}"),
+            }
+
+            match stars::update(&registry.years()) {
+                Ok(_) => eprintln!("Successfully updated README with stars."),
+                Err(e) => eprintln!("Failed to update readme with stars: {e}"),
+            }
+
+            // the table covers all years, so it is only rebuilt from complete runs.
+            if year.is_some() {
+                eprintln!("Skipped README update, run without `--year` to update benchmarks.");
+                return;
+            }
+
+            match readme_benchmarks::update(timings, total_millis, table) {
+                Ok(()) => eprintln!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
@@ -43,212 +148,226 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Runs `run` for every item on up to `jobs` threads.
+/// Results are passed to `report` in the order of `items`, as soon as all preceding items are done.
+fn run_ordered<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    run: impl Fn(&T) -> R + Sync,
+    mut report: impl FnMut(&T, R),
+) {
+    if jobs <= 1 {
+        items.iter().for_each(|item| report(item, run(item)));
+        return;
     }
-}
-
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, run) = (&next, &run);
+
+            thread::Builder::new()
+                .stack_size(runner::STACK_SIZE)
+                .spawn_scoped(scope, move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    if tx.send((i, run(item))).is_err() {
+                        break;
+                    }
+                })
+                .expect("could not spawn worker thread");
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        drop(tx);
 
-        if is_release {
-            args.push("--release");
-        }
+        // buffer results that finished early until it is their turn.
+        let mut pending = BTreeMap::new();
+        let mut next_report = 0;
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_report) {
+                report(&items[next_report], result);
+                next_report += 1;
+            }
         }
+    });
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+/// The records of a day, with the lines its solution printed if it ran in its own process.
+#[derive(Debug, Default)]
+struct DayOutput {
+    lines: Vec<String>,
+    records: Vec<PartRecord>,
+}
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+impl From<Vec<PartRecord>> for DayOutput {
+    fn from(records: Vec<PartRecord>) -> Self {
+        Self {
+            lines: vec![],
+            records,
         }
+    }
+}
 
-        thread.join().unwrap();
-        cmd.wait()?;
+/// Builds the solution bins up front and returns their executables by bin name.
+/// Days run these directly, so they do not go through cargo or wait for each other's builds.
+/// Solutions that failed to build have no executable, this is reported by the days they belong to.
+fn build_solutions(bins: &[String], is_release: bool) -> HashMap<String, PathBuf> {
+    let mut args = vec![
+        "build",
+        "--quiet",
+        "--message-format=json-render-diagnostics",
+    ];
+
+    for bin in bins {
+        args.extend(["--bin", bin]);
+    }
 
-        Ok(output)
+    if is_release {
+        args.push("--release");
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    if alloc::is_enabled() {
+        args.extend(["--features", "alloc_stats"]);
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    let Ok(output) = solve::cargo().args(&args).stderr(Stdio::inherit()).output() else {
+        return HashMap::new();
+    };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    // cargo describes every built bin with a `compiler-artifact` message.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let message: serde_json::Value = serde_json::from_str(line).ok()?;
+            if message["reason"] != "compiler-artifact" {
+                return None;
+            }
+            let name = message["target"]["name"].as_str()?;
+            let executable = message["executable"].as_str()?;
+            Some((name.to_string(), PathBuf::from(executable)))
+        })
+        .collect()
+}
 
-                timings.total_nanos += nanos;
-            });
+/// Runs a day in its own process and captures what its solution prints.
+fn run_isolated(
+    executable: Option<&Path>,
+    year: Year,
+    day: Day,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Result<DayOutput, String> {
+    // missing inputs are reported like in-process runs, instead of as a failed process.
+    InputSource::Puzzle
+        .read(year, day)
+        .map_err(|e| e.to_string())?;
+
+    let executable = executable.ok_or("the solution could not be built")?;
+
+    let mut lines = vec![];
+    let records = solve::read_records(Command::new(executable), is_timed, timeout, |line| {
+        lines.push(line);
+    })
+    .ok_or("the solution could not be run")?;
+
+    Ok(DayOutput { lines, records })
+}
 
-        timings
+fn print_day(
+    year: Year,
+    day: Day,
+    output: Option<Result<DayOutput, String>>,
+    is_json: bool,
+    timings: &mut Vec<Timings>,
+    records: &mut Vec<PartRecord>,
+) {
+    if !is_json {
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    let DayOutput {
+        lines,
+        records: output,
+    } = match output {
+        Some(Ok(output)) => output,
+        Some(Err(e)) => {
+            eprintln!("Skipped day {day}: {e}");
+            DayOutput::default()
+        }
+        None => DayOutput::default(),
+    };
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    // in JSON mode, stdout is reserved for the records.
+    if is_json {
+        lines.iter().for_each(|line| eprintln!("{line}"));
+    } else {
+        lines.iter().for_each(|line| println!("{line}"));
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    if output.is_empty() {
+        if !is_json {
+            println!("Not solved.");
+        }
+    } else {
+        if !is_json {
+            output.iter().for_each(print_record);
+        }
+        timings.push(readme_benchmarks::collect_timings(&output, year, day));
+        records.extend(output);
     }
+}
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+fn print_record(record: &PartRecord) {
+    let part = record.label();
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    let Some(outcome) = record.outcome() else {
+        runner::print_timeout(&part, record.timing.median);
+        return;
+    };
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    let stats =
+        runner::format_duration(&record.timing) + &runner::format_memory(record.memory.as_ref());
+
+    if record.is_parse_step() && record.status == PartStatus::Solved {
+        println!("{part}:{stats}");
+    } else {
+        runner::print_result(&outcome, &part, &stats);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::run_ordered;
+    use std::time::Duration;
+
+    #[test]
+    fn reports_parallel_results_in_order() {
+        let items: Vec<u64> = (0..16).collect();
+        let mut reported = vec![];
+
+        run_ordered(
+            &items,
+            4,
+            |&i| {
+                // later items finish first.
+                std::thread::sleep(Duration::from_millis(16 - i));
+                i * 2
+            },
+            |&i, result| reported.push((i, result)),
+        );
+
+        assert_eq!(
+            reported,
+            items.iter().map(|&i| (i, i * 2)).collect::<Vec<_>>()
+        );
     }
 }
@@ -0,0 +1,102 @@
+use std::process;
+
+use crate::template::{bench_history, paths, protocol::PartStatus, ANSI_BOLD, ANSI_RESET};
+
+/// Compares the latest benchmark run of this machine with a baseline run.
+/// Exits with a non-zero status if any part regressed beyond `threshold` percent or stopped solving,
+/// or if the runs have no part in common.
+pub fn compare(baseline: Option<&str>, threshold: f64) {
+    let history = match bench_history::load(paths::history_path()) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to load benchmarks: {e}");
+            process::exit(1);
+        }
+    };
+
+    let machine = bench_history::get_machine();
+
+    let Some((baseline, current)) = bench_history::select(&history, &machine, baseline) else {
+        eprintln!("Could not find two benchmark runs to compare for machine \"{machine}\". Run `cargo time` first.");
+        process::exit(1);
+    };
+
+    let label = |rev: &Option<String>| rev.clone().unwrap_or_else(|| "unknown".into());
+
+    // runs of different years have nothing in common, passing them would hide every regression.
+    if baseline.shared_parts(current) == 0 {
+        eprintln!(
+            "No part of {} was benchmarked in the baseline {}, there is nothing to compare. Pass a `--baseline` of the same years.",
+            label(&current.revision),
+            label(&baseline.revision)
+        );
+        process::exit(1);
+    }
+
+    println!(
+        "Comparing {} (baseline) with {} on \"{machine}\"",
+        label(&baseline.revision),
+        label(&current.revision)
+    );
+    println!();
+    println!("{ANSI_BOLD}Year | Day | Part | Baseline | Current | Delta{ANSI_RESET}");
+
+    let deltas = bench_history::compare(baseline, current, threshold);
+
+    for delta in &deltas {
+        let marker = if delta.is_regression { " ✖" } else { "" };
+        let change = delta.percent.map_or_else(
+            || {
+                format!(
+                    "{} → {}",
+                    status_label(delta.baseline_status),
+                    status_label(delta.current_status)
+                )
+            },
+            |percent| format!("{percent:+.1}%"),
+        );
+        println!(
+            "{} | {:02}  | {}    | {} | {} | {change}{marker}",
+            delta.year,
+            delta.day,
+            delta.part,
+            timing(delta.baseline_status, delta.baseline_nanos),
+            timing(delta.current_status, delta.current_nanos),
+        );
+    }
+
+    let regressions = deltas.iter().filter(|d| d.is_regression).count();
+
+    println!();
+    if regressions > 0 {
+        println!(
+            "{ANSI_BOLD}{regressions} part(s) regressed by more than {threshold}% or stopped solving.{ANSI_RESET}"
+        );
+        process::exit(1);
+    } else {
+        println!("No part regressed by more than {threshold}%.");
+    }
+}
+
+/// The median time of a solved part, or the status of a part that did not solve.
+fn timing(status: PartStatus, nanos: u128) -> String {
+    match status {
+        PartStatus::Solved => format!("{:.1?}", self::nanos(nanos)),
+        status => status_label(status).into(),
+    }
+}
+
+fn status_label(status: PartStatus) -> &'static str {
+    match status {
+        PartStatus::Solved => "solved",
+        PartStatus::Unimplemented => "unimplemented",
+        PartStatus::Error => "error",
+        PartStatus::Panic => "panic",
+        PartStatus::Timeout => "timeout",
+    }
+}
+
+fn nanos(n: u128) -> std::time::Duration {
+    #[allow(clippy::cast_possible_truncation)]
+    std::time::Duration::from_nanos(n as u64)
+}
@@ -1,15 +1,18 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, AocClient};
+use crate::{Day, Year};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(year: Year, day: Day) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = aoc_client::download(&client, year, day) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,102 @@
+use std::{fs, process};
+
+use crate::template::{config, examples, paths, puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+
+/// Lines of a code block that are shown when listing it.
+const PREVIEW_LINES: usize = 5;
+
+/// Lists the code blocks of the saved puzzle description, or adds the picked ones to the examples of the day.
+/// Works offline, on the description that `cargo read` or `cargo download` saved.
+pub fn handle(year: Year, day: Day, pick: Option<&[usize]>) {
+    let puzzle_path = paths::puzzle_path(year, day);
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            let year_arg = if config::get().year == Some(year) {
+                String::new()
+            } else {
+                format!(" --year {year}")
+            };
+            eprintln!("Error: could not read puzzle description \"{puzzle_path}\": {e}. Run `cargo read {day}{year_arg}` to download it.");
+            process::exit(1);
+        }
+    };
+
+    let blocks = puzzle::code_blocks(&markdown);
+    if blocks.is_empty() {
+        println!("No code blocks found in \"{puzzle_path}\".");
+        return;
+    }
+
+    let Some(pick) = pick else {
+        for (index, block) in blocks.iter().enumerate() {
+            print_block(index + 1, block);
+        }
+        println!(
+            "{ANSI_ITALIC}Pick blocks with `cargo examples {day} --pick 1,2` to add them to \"{}\".{ANSI_RESET}",
+            paths::examples_path(year, day)
+        );
+        return;
+    };
+
+    let mut picked = vec![];
+    for &index in pick {
+        match blocks.get(index.wrapping_sub(1)) {
+            Some(block) => picked.push(block.to_example(&format!("block {index}"))),
+            None => {
+                eprintln!(
+                    "Error: there is no block {index}, the description has {} blocks.",
+                    blocks.len()
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    match examples::append(year, day, &picked) {
+        Ok(added) => {
+            let examples_path = paths::examples_path(year, day);
+            for name in &added {
+                println!("Added example \"{name}\" to \"{examples_path}\".");
+            }
+            if added.len() < picked.len() {
+                println!("Skipped examples that already exist.");
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn print_block(index: usize, block: &puzzle::CodeBlock) {
+    let part = if block.part == 1 {
+        "part one"
+    } else {
+        "part two"
+    };
+    let lines = block.content.lines().count();
+
+    let answers = [("part one", &block.part_one), ("part two", &block.part_two)]
+        .iter()
+        .filter_map(|(part, answer)| answer.as_ref().map(|answer| format!("{part}: {answer}")))
+        .collect::<Vec<_>>();
+
+    let answers = if answers.is_empty() {
+        String::new()
+    } else {
+        format!(", expects {}", answers.join(", "))
+    };
+
+    println!("{ANSI_BOLD}Block {index}{ANSI_RESET} ({part}, {lines} lines{answers})");
+
+    for line in block.content.lines().take(PREVIEW_LINES) {
+        println!("    {line}");
+    }
+    if lines > PREVIEW_LINES {
+        println!("    ...");
+    }
+    println!();
+}
@@ -1,5 +1,12 @@
 pub mod all;
+pub mod bench;
 pub mod download;
+pub mod examples;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod start;
+pub mod submit;
+pub mod verify;
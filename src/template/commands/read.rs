@@ -1,16 +1,22 @@
 use std::process;
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, AocClient};
+use crate::{Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(year: Year, day: Day) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    match aoc_client::read(&client, year, day) {
+        Ok(puzzle) => println!("{puzzle}"),
+        Err(e) => {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
     };
 }
@@ -0,0 +1,64 @@
+use std::{collections::BTreeMap, fs, process};
+
+use crate::template::{
+    answers::Ledger,
+    bench_history::{self, HistoryEntry},
+    paths, report,
+};
+use crate::{Day, Year};
+
+/// Writes an HTML report of `days`, or of the days of one year, to `output`.
+/// Timings are taken from the benchmark runs of this machine, run `cargo time` to record them.
+pub fn handle(days: &[(Year, Day)], year: Option<Year>, redact: bool, output: &str) {
+    let days: Vec<(Year, Day)> = days
+        .iter()
+        .copied()
+        .filter(|&(y, _)| year.is_none_or(|year| y == year))
+        .collect();
+
+    let mut years: Vec<Year> = days.iter().map(|&(year, _)| year).collect();
+    years.dedup();
+
+    let mut ledgers = BTreeMap::new();
+    for &year in &years {
+        match Ledger::load(paths::ledger_path(year)) {
+            Ok(ledger) => {
+                ledgers.insert(year, ledger);
+            }
+            Err(e) => {
+                eprintln!("Failed to load answers of {year}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let history = match bench_history::load(paths::history_path()) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to load benchmarks: {e}");
+            process::exit(1);
+        }
+    };
+
+    let machine = bench_history::get_machine();
+    let runs: Vec<&HistoryEntry> = history.iter().filter(|e| e.machine == machine).collect();
+
+    let summary = match runs.last() {
+        Some(latest) => format!(
+            "Timings of {} benchmark run(s) on \"{machine}\", the latest at revision {}.",
+            runs.len(),
+            latest.revision.as_deref().unwrap_or("unknown")
+        ),
+        None => format!("No benchmark runs on \"{machine}\" yet, run `cargo time` to record them."),
+    };
+
+    let reports = report::collect(&days, &ledgers, &runs);
+
+    match fs::write(output, report::render(&reports, &summary, redact)) {
+        Ok(()) => println!("Wrote report of {} day(s) to \"{output}\".", days.len()),
+        Err(e) => {
+            eprintln!("Failed to write report to \"{output}\": {e}");
+            process::exit(1);
+        }
+    }
+}
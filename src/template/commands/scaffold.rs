@@ -1,75 +1,207 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{config, paths};
+use crate::{Day, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Templates are rendered by replacing these placeholders.
+const PLACEHOLDERS: [&str; 5] = [
+    "YEAR_NUMBER",
+    "DAY_NUMBER",
+    "STRUCT_NAME",
+    "RETURN_TYPE",
+    "PARSE_SKELETON",
+];
 
-pub fn part_one(input: &str) -> Option<u32> {
+const DEFAULT_TEMPLATE: &str = "default";
+const DEFAULT_RETURN_TYPE: &str = "u32";
+
+/// Parses the lines of the input, the starting point of `parse` in templates that use [`crate::Solution`].
+const PARSE_SKELETON: &str = "input.lines().map(str::to_string).collect()";
+
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Option<RETURN_TYPE> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<RETURN_TYPE> {
     None
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    // a test for every example in the examples file of the day.
+    advent_of_code::example_tests!();
+}
+"#;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+const PARSED_STRUCT_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, STRUCT_NAME);
+
+pub struct STRUCT_NAME;
+
+impl advent_of_code::Solution for STRUCT_NAME {
+    type Parsed = Vec<String>;
+    type PartOne = Option<RETURN_TYPE>;
+    type PartTwo = Option<RETURN_TYPE>;
+
+    fn parse(input: &str) -> Self::Parsed {
+        PARSE_SKELETON
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+        None
     }
+
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // a test for every example in the examples file of the day.
+    advent_of_code::example_tests!();
 }
 "#;
 
+const BUILTIN_TEMPLATES: [(&str, &str); 2] = [
+    (DEFAULT_TEMPLATE, MODULE_TEMPLATE),
+    ("parsed-struct", PARSED_STRUCT_TEMPLATE),
+];
+
+pub(crate) const EXAMPLES_HEADER: &str =
+    "# Examples of the puzzle, a test is generated for each of them.
+# Answers that are left out are not checked.
+";
+
+const EXAMPLE_PLACEHOLDER: &str = r#"[[example]]
+name = "example"
+# part_one = 0
+# part_two = 0
+input = '''
+'''
+"#;
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new().write(true).create(true).open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Finds a template by name, templates in the project directory take precedence over the built-in ones.
+fn load_template(name: &str) -> Result<String, String> {
+    let path = paths::template_path(name);
+    match fs::read_to_string(&path) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            return Err(format!("could not read template \"{path}\": {e}"));
+        }
+        Err(_) => {}
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            let mut names: Vec<String> = BUILTIN_TEMPLATES
+                .iter()
+                .map(|(name, _)| (*name).to_string())
+                .chain(paths::template_names())
+                .collect();
+            names.sort_unstable();
+            names.dedup();
+            format!(
+                "unknown template `{name}`, available templates: {}",
+                names.join(", ")
+            )
+        })
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+fn render(template: &str, year: Year, day: Day, return_type: &str) -> String {
+    let values = [
+        year.into_inner().to_string(),
+        day.into_inner().to_string(),
+        format!("Day{day}"),
+        return_type.to_string(),
+        PARSE_SKELETON.to_string(),
+    ];
+
+    PLACEHOLDERS
+        .iter()
+        .zip(values)
+        .fold(template.to_string(), |module, (placeholder, value)| {
+            module.replace(placeholder, &value)
+        })
+}
+
+/// Parses the return type of the parts, e.g. `u64` or `String`.
+pub fn parse_return_type(s: &str) -> Result<String, String> {
+    let is_valid = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '<' | '>' | ','));
+
+    if is_valid {
+        Ok(s.to_string())
+    } else {
+        Err(format!("invalid return type `{s}`"))
+    }
+}
+
+/// Creates the module and data files of a day from the template `template`, or the default template.
+pub fn handle(year: Year, day: Day, template: Option<&str>, return_type: Option<&str>) {
+    create_files(year, day, template, return_type, false);
+
+    println!("---");
+    if config::get().year == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+}
+
+/// Creates the files of a day, an existing module file is an error unless `keep_module` is set.
+pub(crate) fn create_files(
+    year: Year,
+    day: Day,
+    template: Option<&str>,
+    return_type: Option<&str>,
+    keep_module: bool,
+) {
+    let input_path = paths::input_path(year, day);
+    let examples_path = paths::examples_path(year, day);
+    let module_path = paths::bin_path(year, day);
+
+    let template = match load_template(template.unwrap_or(DEFAULT_TEMPLATE)) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Error: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    if keep_module && Path::new(&module_path).exists() {
+        println!("Kept existing module file \"{}\"", &module_path);
+    } else {
+        write_module(&module_path, &template, year, day, return_type);
     }
 
+    let has_input = Path::new(&input_path).exists();
     match create_file(&input_path) {
+        Ok(_) if has_input => {
+            println!("Kept existing input file \"{}\"", &input_path);
+        }
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
@@ -79,16 +211,96 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    // examples that were already added are kept.
+    if Path::new(&examples_path).exists() {
+        println!("Kept existing examples file \"{}\"", &examples_path);
+    } else {
+        match create_file(&examples_path).and_then(|mut file| {
+            file.write_all(format!("{EXAMPLES_HEADER}\n{EXAMPLE_PLACEHOLDER}").as_bytes())
+        }) {
+            Ok(()) => {
+                println!("Created examples file \"{}\"", &examples_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create examples file: {e}");
+                process::exit(1);
+            }
         }
+    }
+}
+
+fn write_module(
+    module_path: &str,
+    template: &str,
+    year: Year,
+    day: Day,
+    return_type: Option<&str>,
+) {
+    let mut file = match safe_create_file(module_path) {
+        Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to create module file: {e}");
+            process::exit(1);
+        }
+    };
+
+    match file.write_all(
+        render(
+            template,
+            year,
+            day,
+            return_type.unwrap_or(DEFAULT_RETURN_TYPE),
+        )
+        .as_bytes(),
+    ) {
+        Ok(()) => {
+            println!("Created module file \"{module_path}\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
+}
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load_template, parse_return_type, render, MODULE_TEMPLATE};
+    use crate::{day, year};
+
+    #[test]
+    fn renders_default_template() {
+        let module = render(MODULE_TEMPLATE, year!(2023), day!(5), "u64");
+
+        assert!(module.starts_with("advent_of_code::solution!(2023, 5);"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+        assert!(!module.contains("RETURN_TYPE"));
+    }
+
+    #[test]
+    fn renders_parsed_struct_template() {
+        let module = render(
+            &load_template("parsed-struct").unwrap(),
+            year!(2023),
+            day!(5),
+            "i64",
+        );
+
+        assert!(module.starts_with("advent_of_code::solution!(2023, 5, Day05);"));
+        assert!(module.contains("impl advent_of_code::Solution for Day05 {"));
+        assert!(module.contains("type PartTwo = Option<i64>;"));
+        assert!(module.contains("        input.lines().map(str::to_string).collect()\n"));
+    }
+
+    #[test]
+    fn rejects_unknown_templates() {
+        let e = load_template("missing").unwrap_err();
+        assert!(e.contains("default, parsed-struct"));
+    }
+
+    #[test]
+    fn validates_return_types() {
+        assert_eq!(parse_return_type("usize").unwrap(), "usize");
+        assert!(parse_return_type("u64; panic!()").is_err());
+    }
 }
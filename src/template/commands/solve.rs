@@ -1,14 +1,46 @@
-use std::process::{Command, Stdio};
+use std::{
+    env,
+    io::{BufRead, BufReader},
+    process::{self, Command, Stdio},
+    time::Duration,
+};
 
-use crate::Day;
+use crate::template::{
+    alloc,
+    input::InputSource,
+    paths,
+    protocol::{PartRecord, PartStatus, JSON_FLAG},
+    runner::TIMEOUT_FLAG,
+    watch::Watcher,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    timeout: Option<Duration>,
+    source: &InputSource,
+) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        paths::bin_name(year, day),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    // solutions only report heap usage if they are built with the counting allocator as well.
+    if alloc::is_enabled() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("alloc_stats".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -20,12 +52,261 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(timeout) = timeout {
+        cmd_args.push(TIMEOUT_FLAG.to_string());
+        cmd_args.push(timeout.as_secs_f64().to_string());
+    }
+
+    cmd_args.extend(source.to_args());
+
+    let mut cmd = cargo()
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // a solution that panicked or could not be built fails the command as well.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Re-runs the solution and example tests of a day whenever its solution, input or example changes.
+pub fn watch(year: Year, day: Day, release: bool, timeout: Option<Duration>) {
+    let files = [
+        paths::bin_path(year, day),
+        paths::input_path(year, day),
+        paths::example_path(year, day),
+        paths::examples_path(year, day),
+    ];
+
+    let mut watcher = Watcher::new(&files);
+    let mut previous: Vec<PartRecord> = vec![];
+
+    loop {
+        match run_records(year, day, release, false, timeout, |line| {
+            println!("{line}")
+        }) {
+            Some(records) if !records.is_empty() => {
+                diff_answers(&previous, &records)
+                    .iter()
+                    .for_each(|line| println!("{line}"));
+                previous = records;
+            }
+            _ => println!("{ANSI_BOLD}Run failed,{ANSI_RESET} keeping previous answers."),
+        }
+
+        if run_tests(year, day, release) {
+            println!("Example tests: ✔ passed");
+        } else {
+            println!("Example tests: ✖ failed");
+        }
+
+        println!(
+            "\n{ANSI_ITALIC}Watching {} for changes...{ANSI_RESET}",
+            files.join(", ")
+        );
+
+        let changed = watcher.wait(Duration::from_millis(500));
+
+        println!(
+            "\n{ANSI_BOLD}Changed:{ANSI_RESET} {}",
+            changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+/// Runs the solution bin and collects the records of its parts, any other line it prints is passed to `on_line`.
+/// Returns [`None`] if the solution could not be built or run.
+pub(crate) fn run_records(
+    year: Year,
+    day: Day,
+    release: bool,
+    time: bool,
+    timeout: Option<Duration>,
+    on_line: impl FnMut(String),
+) -> Option<Vec<PartRecord>> {
+    let bin_name = paths::bin_name(year, day);
+    let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+    if release {
+        args.push("--release");
+    }
+
+    if alloc::is_enabled() {
+        args.extend(["--features", "alloc_stats"]);
+    }
+
+    args.push("--");
+
+    let mut cmd = cargo();
+    cmd.args(&args);
+    read_records(cmd, time, timeout, on_line)
+}
+
+/// Runs a solution in JSON mode and collects the records of its parts, any other line it prints is passed to `on_line`.
+/// `cmd` is the command that starts the solution, the arguments of the solution are appended to it.
+/// Returns [`None`] if the solution could not be run.
+pub(crate) fn read_records(
+    mut cmd: Command,
+    time: bool,
+    timeout: Option<Duration>,
+    mut on_line: impl FnMut(String),
+) -> Option<Vec<PartRecord>> {
+    cmd.arg(JSON_FLAG);
+
+    if time {
+        cmd.arg("--time");
+    }
+
+    if let Some(timeout) = timeout {
+        cmd.args([TIMEOUT_FLAG, &timeout.as_secs_f64().to_string()]);
+    }
+
+    let mut cmd = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .ok()?;
+
+    let stdout = BufReader::new(cmd.stdout.take()?);
+    let mut records = vec![];
+
+    for line in stdout.lines().map_while(Result::ok) {
+        match PartRecord::parse(&line) {
+            Some(record) => records.push(record),
+            None => on_line(line),
+        }
+    }
+
+    cmd.wait().ok()?.success().then_some(records)
+}
+
+/// A `cargo` command to build or run solutions from a command that was itself started by `cargo run`.
+/// Cargo describes the package to the processes it runs with environment variables. They are removed,
+/// so solutions are built in the same environment as the command and dependencies are not rebuilt every time.
+pub(crate) fn cargo() -> Command {
+    let mut cmd = Command::new("cargo");
+
+    for (key, value) in env::vars() {
+        let is_package_var = key == "OUT_DIR"
+            || key.starts_with("CARGO_PKG_")
+            || key.starts_with("CARGO_BIN_EXE_")
+            || matches!(
+                key.as_str(),
+                "CARGO_MANIFEST_DIR"
+                    | "CARGO_MANIFEST_PATH"
+                    | "CARGO_CRATE_NAME"
+                    | "CARGO_BIN_NAME"
+                    | "CARGO_PRIMARY_PACKAGE"
+            );
+        // the build script passes the year of the config on, an unchanged year is read from the config again.
+        let is_build_year = key == "AOC_YEAR" && option_env!("AOC_YEAR") == Some(value.as_str());
+
+        if is_package_var || is_build_year {
+            cmd.env_remove(key);
+        }
+    }
+
+    cmd
+}
+
+fn run_tests(year: Year, day: Day, release: bool) -> bool {
+    let bin_name = paths::bin_name(year, day);
+    let mut args = vec!["test", "--quiet", "--bin", &bin_name];
+
+    if release {
+        args.push("--release");
+    }
+
+    cargo()
+        .args(&args)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Describes the answer of each part compared to the previous run.
+fn diff_answers(previous: &[PartRecord], current: &[PartRecord]) -> Vec<String> {
+    current
+        .iter()
+        .map(|record| {
+            let label = record.label();
+
+            let Some(outcome) = record.outcome() else {
+                return format!("{label}: ⏱ timeout after {:.1?}", record.timing.median);
+            };
+
+            if record.is_parse_step() && record.status == PartStatus::Solved {
+                return format!("{label}: ({:.1?})", record.timing.median);
+            }
+
+            // failures are shown in full, but compared by their answer only.
+            if outcome.answer().is_none() {
+                return format!("{label}: {outcome}");
+            }
+
+            let answer = record.answer.as_deref().unwrap_or("✖");
+            let before = previous
+                .iter()
+                .find(|r| r.part == record.part)
+                .map(|r| r.answer.as_deref().unwrap_or("✖"));
+
+            let change = match before {
+                None => String::new(),
+                Some(before) if before == answer => " (unchanged)".into(),
+                Some(before) => format!(" (was {before})"),
+            };
+
+            format!(
+                "{label}: {ANSI_BOLD}{answer}{ANSI_RESET}{change} ({:.1?})",
+                record.timing.median
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::diff_answers;
+    use crate::template::{protocol::PartRecord, runner::Stats, ANSI_BOLD, ANSI_RESET};
+    use crate::{day, year};
+    use std::time::Duration;
+
+    fn record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord::new(
+            year!(2023),
+            day!(1),
+            part,
+            answer.map(Into::into),
+            Stats::single(Duration::from_micros(5)),
+        )
+    }
+
+    #[test]
+    fn diffs_answers_against_previous_run() {
+        let previous = [record(1, Some("142")), record(2, None)];
+        let current = [record(1, Some("142")), record(2, Some("281"))];
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec![
+                format!("Part 1: {ANSI_BOLD}142{ANSI_RESET} (unchanged) (5.0µs)"),
+                format!("Part 2: {ANSI_BOLD}281{ANSI_RESET} (was ✖) (5.0µs)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn omits_diff_on_first_run() {
+        assert_eq!(
+            diff_answers(&[], &[record(1, Some("142"))]),
+            vec![format!("Part 1: {ANSI_BOLD}142{ANSI_RESET} (5.0µs)")]
+        );
+    }
 }
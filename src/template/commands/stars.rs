@@ -0,0 +1,29 @@
+use std::process;
+
+use crate::template::{answers::Ledger, paths, readme_stars};
+use crate::Year;
+
+/// Fills the progress table of the readme with the stars of all years with solutions.
+pub fn handle(years: &[Year]) {
+    match update(years) {
+        Ok(stars) => println!("Successfully updated README with {stars} star(s)."),
+        Err(e) => {
+            eprintln!("Failed to update readme with stars: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Rebuilds the progress table from the answer ledgers and returns the number of stars.
+pub(crate) fn update(years: &[Year]) -> Result<usize, String> {
+    let ledgers = years
+        .iter()
+        .map(|&year| {
+            Ledger::load(paths::ledger_path(year))
+                .map(|ledger| (year, ledger))
+                .map_err(|e| e.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    readme_stars::update(&ledgers).map_err(|e| e.to_string())
+}
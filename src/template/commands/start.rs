@@ -0,0 +1,188 @@
+use std::{
+    fs,
+    io::{self, Write},
+    process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{
+    aoc_client::{self, AocClient},
+    clock::{self, Clock, SystemClock},
+    commands::scaffold,
+    examples, paths, puzzle, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, Year};
+
+/// Puzzles unlock at midnight in the UTC-5 time zone of the Advent of Code servers.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// Days between the unix epoch and a date of the gregorian calendar.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The time at which the puzzle of a day unlocks.
+#[must_use]
+pub fn unlock_time(year: Year, day: Day) -> SystemTime {
+    let days = days_from_civil(
+        i64::from(year.into_inner()),
+        12,
+        i64::from(day.into_inner()),
+    );
+
+    #[allow(clippy::cast_sign_loss)]
+    let seconds = days as u64 * 86_400 + UNLOCK_HOUR_UTC * 3_600;
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+/// Counts down to `unlock` on `out` once per second, returns right away if it has passed.
+pub fn wait_until(
+    clock: &mut impl Clock,
+    unlock: SystemTime,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    clock::countdown(clock, unlock, "Unlocks in", out)?;
+    writeln!(out, "\r🔓 Unlocked!{}", " ".repeat(16))
+}
+
+/// Sets up a day in one go: scaffolds it, downloads input and description, extracts its examples and shows the puzzle.
+/// Steps that were done before are skipped, so the command can be run again, e.g. to fetch the description of part two.
+pub fn handle(year: Year, day: Day, wait: bool, template: Option<&str>, return_type: Option<&str>) {
+    if wait {
+        if let Err(e) = wait_until(&mut SystemClock, unlock_time(year, day), &mut io::stdout()) {
+            eprintln!("Failed to wait for the puzzle: {e}");
+            process::exit(1);
+        }
+    }
+
+    scaffold::create_files(year, day, template, return_type, true);
+
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = paths::input_path(year, day);
+    if fs::read_to_string(&input_path).is_ok_and(|input| !input.trim().is_empty()) {
+        println!("Kept existing input \"{input_path}\"");
+    } else {
+        match aoc_client::download_input(&client, year, day) {
+            Ok(path) => println!("🎄 Successfully wrote input to \"{path}\"."),
+            Err(e) => {
+                eprintln!("failed to download input of day {day}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let puzzle = match aoc_client::read(&client, year, day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    extract_examples(year, day, &puzzle);
+
+    println!("---");
+    println!("{puzzle}");
+    println!("---");
+    println!("{ANSI_BOLD}🎄 Puzzle of day {day} is ready.{ANSI_RESET}");
+}
+
+/// Replaces the placeholder examples of a day with the code blocks of the description that come with answers.
+/// Falls back to the first code block, examples that have an input are never replaced.
+fn extract_examples(year: Year, day: Day, puzzle: &str) {
+    let examples_path = paths::examples_path(year, day);
+
+    match examples::load(year, day) {
+        Ok(existing) if existing.iter().any(|e| !e.input.trim().is_empty()) => {
+            println!("Kept existing examples \"{examples_path}\"");
+            return;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Kept examples \"{examples_path}\" that could not be read: {e}");
+            return;
+        }
+    }
+
+    let blocks = puzzle::code_blocks(puzzle);
+    let mut picked: Vec<_> = blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| block.part_one.is_some() || block.part_two.is_some())
+        .collect();
+    if picked.is_empty() {
+        picked = blocks.iter().enumerate().take(1).collect();
+    }
+
+    if picked.is_empty() {
+        println!("No examples found in the description, add them to \"{examples_path}\".");
+        return;
+    }
+
+    let picked: Vec<_> = picked
+        .into_iter()
+        .map(|(index, block)| block.to_example(&format!("block {}", index + 1)))
+        .collect();
+
+    let result = fs::write(&examples_path, format!("{}\n", scaffold::EXAMPLES_HEADER))
+        .map_err(examples::Error::from)
+        .and_then(|()| examples::append(year, day, &picked));
+
+    match result {
+        Ok(added) => println!(
+            "Added {} example(s) to \"{examples_path}\", check them before relying on the tests.",
+            added.len()
+        ),
+        Err(e) => {
+            eprintln!("Failed to write examples: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{unlock_time, wait_until};
+    use crate::template::clock::FakeClock;
+    use crate::{day, year};
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(year!(2023), day!(1)),
+            UNIX_EPOCH + Duration::from_secs(1_701_406_800)
+        );
+        // 2015-12-25T05:00:00Z
+        assert_eq!(
+            unlock_time(year!(2015), day!(25)),
+            UNIX_EPOCH + Duration::from_secs(1_451_019_600)
+        );
+    }
+
+    #[test]
+    fn announces_unlock() {
+        let unlock = unlock_time(year!(2023), day!(1));
+        let mut clock = FakeClock::at(unlock - Duration::from_secs(1));
+        let mut out = vec![];
+
+        wait_until(&mut clock, unlock, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("Unlocked!                \n"));
+    }
+}
@@ -0,0 +1,111 @@
+use std::{io, process, time::SystemTime};
+
+use crate::template::{
+    answers::Ledger,
+    aoc_client::AocClient,
+    clock::SystemClock,
+    paths,
+    submissions::{self, Pending, Queue},
+};
+
+/// Lists the answers that wait for a rate limit to pass, or submits them with `flush`.
+/// Answers are submitted in the order they were queued, each after its cooldown.
+pub fn handle(flush: bool) {
+    let queue_path = paths::queue_path();
+    let queue = match Queue::load(&queue_path) {
+        Ok(queue) => queue,
+        Err(e) => {
+            eprintln!("Failed to load submissions: {e}");
+            process::exit(1);
+        }
+    };
+
+    if queue.submissions.is_empty() {
+        println!("No answers are waiting to be submitted.");
+        return;
+    }
+
+    if !flush {
+        let now = SystemTime::now();
+        for pending in &queue.submissions {
+            match pending.not_before().duration_since(now) {
+                Ok(wait) => println!("{pending} (cooldown ends in {}s)", wait.as_secs()),
+                Err(_) => println!("{pending} (ready)"),
+            }
+        }
+        println!("Run `cargo submit --flush` to submit them.");
+        return;
+    }
+
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let failed = queue
+        .submissions
+        .into_iter()
+        .filter(|pending| !flush_one(&client, &queue_path, pending))
+        .count();
+
+    if failed > 0 {
+        eprintln!("{failed} answer(s) could not be submitted and stay queued.");
+        process::exit(1);
+    }
+}
+
+/// Submits one queued answer and records its verdict, returns whether it left the queue.
+fn flush_one(client: &AocClient, queue_path: &str, pending: &Pending) -> bool {
+    let (Some(year), Some(day)) = (pending.year(), pending.day()) else {
+        eprintln!("Skipped invalid submission \"{pending}\".");
+        return false;
+    };
+
+    let ledger_path = paths::ledger_path(year);
+    let mut ledger = match Ledger::load(&ledger_path) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Failed to load answers of {year}: {e}");
+            return false;
+        }
+    };
+
+    // the answer may have been rejected or another one accepted since it was queued.
+    if let Err(rejection) = ledger.check(day, pending.part, &pending.answer) {
+        println!("Dropped {pending}: {rejection}");
+        let result = Queue::load(queue_path).and_then(|mut queue| {
+            queue.remove(pending);
+            queue.save(queue_path)
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to update submissions: {e}");
+            return false;
+        }
+        return true;
+    }
+
+    println!("Submitting {pending}...");
+    match submissions::submit(
+        client,
+        &mut SystemClock,
+        queue_path,
+        pending.clone(),
+        &mut io::stdout(),
+    ) {
+        Ok(outcome) => {
+            println!("{outcome}");
+            ledger.record(day, pending.part, &pending.answer, &outcome);
+            if let Err(e) = ledger.save(&ledger_path) {
+                eprintln!("Failed to record answer: {e}");
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to submit {pending}: {e}");
+            false
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::{fmt::Display, process};
+
+use crate::template::{answers::Ledger, paths, registry::Registry, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day, Year};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// The part has an accepted answer, but the solution could not be run, e.g. because its input is missing.
+    NotRun,
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "✔ pass"),
+            Status::Fail {
+                expected,
+                actual: Some(actual),
+            } => write!(f, "✖ fail (expected {expected}, got {actual})"),
+            Status::Fail {
+                expected,
+                actual: None,
+            } => write!(f, "✖ fail (expected {expected}, got nothing)"),
+            Status::NotRun => write!(f, "✖ not run"),
+            Status::Missing => write!(f, "- missing"),
+        }
+    }
+}
+
+/// Compares the answer of a solution with the accepted answer of the ledger.
+fn verify_part(expected: Option<&str>, actual: Option<&str>) -> Status {
+    match (expected, actual) {
+        (None, _) => Status::Missing,
+        (Some(expected), Some(actual)) if expected == actual => Status::Pass,
+        (Some(expected), actual) => Status::Fail {
+            expected: expected.into(),
+            actual: actual.map(Into::into),
+        },
+    }
+}
+
+/// Verifies all days of a year, or of all registered years if no year is given.
+pub fn handle(registry: &Registry, year: Option<Year>) {
+    let years = year.map_or_else(|| registry.years(), |year| vec![year]);
+
+    println!("{ANSI_BOLD}Year | Day | Part 1 | Part 2{ANSI_RESET}");
+
+    let rows: Vec<(Year, Day, [Status; 2])> = years
+        .into_iter()
+        .flat_map(|year| match Ledger::load(paths::ledger_path(year)) {
+            Ok(ledger) => verify_year(registry, year, &ledger),
+            Err(e) => {
+                eprintln!("Failed to load answers of {year}: {e}");
+                process::exit(1);
+            }
+        })
+        .collect();
+
+    let count =
+        |f: fn(&Status) -> bool| rows.iter().flat_map(|(_, _, s)| s).filter(|s| f(s)).count();
+    let passed = count(|s| matches!(s, Status::Pass));
+    let failed = count(|s| matches!(s, Status::Fail { .. }));
+    let not_run = count(|s| matches!(s, Status::NotRun));
+    let missing = count(|s| matches!(s, Status::Missing));
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {not_run} not run, {missing} missing."
+    );
+
+    if failed > 0 || not_run > 0 {
+        process::exit(1);
+    }
+}
+
+/// Verifies all registered days of a year.
+/// Parts with an accepted answer are [`Status::NotRun`] if their day could not be run.
+fn verify_year(registry: &Registry, year: Year, ledger: &Ledger) -> Vec<(Year, Day, [Status; 2])> {
+    let mut rows = vec![];
+
+    for day in all_days() {
+        // days that have not been scaffolded yet are skipped.
+        let Some(solution) = registry.get(year, day) else {
+            continue;
+        };
+
+        let output = solution
+            .run(false, None)
+            .inspect_err(|e| eprintln!("Could not run day {day} of {year}: {e}"));
+
+        let statuses = [1, 2].map(|part| {
+            let expected = ledger.accepted(day, part);
+            let Ok(output) = &output else {
+                return expected.map_or(Status::Missing, |_| Status::NotRun);
+            };
+            let answer = output
+                .iter()
+                .find(|r| r.part == part)
+                .and_then(|r| r.answer.as_deref());
+            verify_part(expected, answer)
+        });
+
+        println!("{year} | {day}  | {} | {}", statuses[0], statuses[1]);
+        rows.push((year, day, statuses));
+    }
+
+    rows
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify_part, verify_year, Status};
+    use crate::template::{
+        answers::Ledger, aoc_client::SubmissionOutcome, registry::Entry, registry::Registry,
+        runner::part_records,
+    };
+    use crate::{day, year};
+
+    #[test]
+    fn passes_matching_answers() {
+        assert_eq!(verify_part(Some("42"), Some("42")), Status::Pass);
+    }
+
+    #[test]
+    fn fails_changed_answers() {
+        assert_eq!(
+            verify_part(Some("42"), Some("43")),
+            Status::Fail {
+                expected: "42".into(),
+                actual: Some("43".into())
+            }
+        );
+        assert_eq!(
+            verify_part(Some("42"), None),
+            Status::Fail {
+                expected: "42".into(),
+                actual: None
+            }
+        );
+    }
+
+    #[test]
+    fn reports_missing_answers() {
+        assert_eq!(verify_part(None, Some("42")), Status::Missing);
+        assert_eq!(verify_part(None, None), Status::Missing);
+    }
+
+    #[test]
+    fn fails_days_that_could_not_run() {
+        // no input is downloaded for this year.
+        let registry = Registry::new([Entry {
+            year: year!(2015),
+            day: day!(1),
+            records: |input, is_timed, timeout| {
+                part_records(
+                    |input: &str| Some(input.len()),
+                    |input: &str| Some(input.len()),
+                    input,
+                    year!(2015),
+                    day!(1),
+                    is_timed,
+                    timeout,
+                )
+            },
+            timeout: None,
+        }]);
+
+        let mut ledger = Ledger::default();
+        ledger.record(day!(1), 1, "42", &SubmissionOutcome::Correct);
+
+        assert_eq!(
+            verify_year(&registry, year!(2015), &ledger),
+            vec![(year!(2015), day!(1), [Status::NotRun, Status::Missing])]
+        );
+    }
+}
@@ -0,0 +1,371 @@
+/// Settings of the project, read from `aoc.toml` in the project root.
+/// Every setting can be overridden with an environment variable, which the binary sets for `--set <key>=<value>`,
+/// so the solutions it runs pick up the same settings.
+use std::{
+    collections::BTreeMap, env, fmt::Display, fs, io, path::PathBuf, sync::OnceLock, time::Duration,
+};
+
+use serde::{de, Deserialize, Deserializer};
+
+use crate::template::cli;
+use crate::Year;
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+/// Selects another config file than [`CONFIG_PATH`].
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+/// The settings that can be overridden, with the environment variables that override them.
+pub const SETTINGS: &[(&str, &str)] = &[
+    ("year", "AOC_YEAR"),
+    ("data_dir", "AOC_DATA_DIR"),
+    ("readme", "AOC_README"),
+    ("session_file", "AOC_SESSION_FILE"),
+    ("bench.budget", "AOC_BENCH_BUDGET"),
+    ("bench.min_samples", "AOC_BENCH_MIN_SAMPLES"),
+    ("bench.max_samples", "AOC_BENCH_MAX_SAMPLES"),
+];
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+    UnknownSetting(String),
+    InvalidValue { key: String, reason: String },
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse config: {e}"),
+            Error::IO(e) => write!(f, "could not read config: {e}"),
+            Error::UnknownSetting(key) => {
+                let keys: Vec<_> = SETTINGS.iter().map(|(key, _)| *key).collect();
+                write!(
+                    f,
+                    "unknown setting `{key}`, expecting one of `{}`",
+                    keys.join("`, `")
+                )
+            }
+            Error::InvalidValue { key, reason } => write!(f, "invalid `{key}`: {reason}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The default year of all commands.
+    #[serde(deserialize_with = "year")]
+    pub year: Option<Year>,
+    /// The root of inputs, examples, descriptions, answers and benchmarks.
+    pub data_dir: String,
+    /// The readme with the benchmark and stars tables.
+    pub readme: String,
+    /// The file with the session cookie, used if `ADVENT_OF_CODE_SESSION` is not set.
+    /// A leading `~` stands for the home directory.
+    pub session_file: String,
+    pub bench: BenchConfig,
+    /// Flags that are passed to a command unless they are given on the command line, e.g. `solve = ["--release"]`.
+    pub defaults: BTreeMap<String, Vec<String>>,
+}
+
+/// How long `--time` benchmarks each part.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchConfig {
+    /// The execution time in seconds that a part is benchmarked for, within the sample limits.
+    pub budget: f64,
+    pub min_samples: u64,
+    pub max_samples: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: "data".into(),
+            readme: "README.md".into(),
+            session_file: "~/.adventofcode.session".into(),
+            bench: BenchConfig::default(),
+            defaults: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: 1.0,
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+impl BenchConfig {
+    #[must_use]
+    pub fn budget(&self) -> Duration {
+        Duration::from_secs_f64(self.budget)
+    }
+}
+
+fn year<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Year>, D::Error> {
+    Option::<u16>::deserialize(deserializer)?
+        .map(|year| {
+            Year::new(year).ok_or_else(|| de::Error::custom("expecting a year from 2015 to 9999"))
+        })
+        .transpose()
+}
+
+impl Config {
+    fn parse(s: &str) -> Result<Self, Error> {
+        toml::from_str(s).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    /// Overrides a setting, `key` is one of [`SETTINGS`].
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let invalid = |reason: &str| Error::InvalidValue {
+            key: key.into(),
+            reason: format!("{reason}, got `{value}`"),
+        };
+
+        match key {
+            "year" => {
+                let year = value.parse::<Year>().map_err(|e| invalid(&e.to_string()))?;
+                self.year = Some(year);
+            }
+            "data_dir" => self.data_dir = value.into(),
+            "readme" => self.readme = value.into(),
+            "session_file" => self.session_file = value.into(),
+            "bench.budget" => {
+                self.bench.budget = value
+                    .parse()
+                    .map_err(|_| invalid("expecting a number of seconds"))?;
+            }
+            "bench.min_samples" => {
+                self.bench.min_samples = value
+                    .parse()
+                    .map_err(|_| invalid("expecting a number of samples"))?;
+            }
+            "bench.max_samples" => {
+                self.bench.max_samples = value
+                    .parse()
+                    .map_err(|_| invalid("expecting a number of samples"))?;
+            }
+            _ => return Err(Error::UnknownSetting(key.into())),
+        }
+
+        Ok(())
+    }
+
+    /// Applies the environment variables of [`SETTINGS`] that `lookup` finds.
+    fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<(), Error> {
+        for (key, var) in SETTINGS {
+            if let Some(value) = lookup(var) {
+                self.set(key, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let invalid = |key: &str, reason: String| {
+            Err(Error::InvalidValue {
+                key: key.into(),
+                reason,
+            })
+        };
+
+        if !self.bench.budget.is_finite() || self.bench.budget < 0.0 {
+            return invalid(
+                "bench.budget",
+                "expecting a positive number of seconds".into(),
+            );
+        }
+        if self.bench.min_samples == 0 {
+            return invalid("bench.min_samples", "expecting at least one sample".into());
+        }
+        if self.bench.min_samples > self.bench.max_samples {
+            return invalid(
+                "bench.max_samples",
+                format!(
+                    "expecting at least `bench.min_samples` ({})",
+                    self.bench.min_samples
+                ),
+            );
+        }
+        if let Some(name) = self.defaults.keys().find(|name| cli::find(name).is_none()) {
+            return invalid("defaults", format!("unknown command `{name}`"));
+        }
+
+        Ok(())
+    }
+
+    /// The session file with `~` expanded to the home directory.
+    #[must_use]
+    pub fn session_path(&self) -> Option<PathBuf> {
+        match self.session_file.strip_prefix('~') {
+            Some(rest) => {
+                let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+                Some(PathBuf::from(home).join(rest.trim_start_matches(['/', '\\'])))
+            }
+            None => Some(PathBuf::from(&self.session_file)),
+        }
+    }
+}
+
+/// The path of the config file, [`CONFIG_PATH`] unless [`CONFIG_ENV`] is set.
+#[must_use]
+pub fn path() -> String {
+    env::var(CONFIG_ENV).unwrap_or_else(|_| CONFIG_PATH.into())
+}
+
+/// The environment variable of a setting, [`None`] if there is no such setting.
+#[must_use]
+pub fn env_var(key: &str) -> Option<&'static str> {
+    SETTINGS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, var)| *var)
+}
+
+/// Reads the config file and applies the overrides of the environment.
+/// A missing config file is treated as an empty one.
+pub fn load() -> Result<Config, Error> {
+    let mut config = match fs::read_to_string(path()) {
+        Ok(s) => Config::parse(&s)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+        Err(e) => return Err(e.into()),
+    };
+
+    config.apply_overrides(|var| env::var(var).ok())?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// The config of the project, loaded on first use.
+///
+/// # Panics
+/// If the config is invalid. The binary reports invalid configs before it runs a command.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| load().unwrap_or_else(|e| panic!("{e}")))
+}
+
+/// Uses `config` for the rest of the process, unless a config was used already.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{BenchConfig, Config, Error};
+    use crate::year;
+
+    #[test]
+    fn empty_config_has_defaults() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.data_dir, "data");
+        assert_eq!(config.readme, "README.md");
+        assert_eq!(config.bench.budget().as_secs(), 1);
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+year = 2022
+data_dir = "puzzles"
+readme = "docs/README.md"
+session_file = "/run/secrets/aoc"
+
+[bench]
+budget = 0.5
+min_samples = 5
+max_samples = 50
+
+[defaults]
+solve = ["--release", "--timeout", "10"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: Some(year!(2022)),
+                data_dir: "puzzles".into(),
+                readme: "docs/README.md".into(),
+                session_file: "/run/secrets/aoc".into(),
+                bench: BenchConfig {
+                    budget: 0.5,
+                    min_samples: 5,
+                    max_samples: 50,
+                },
+                defaults: BTreeMap::from([(
+                    "solve".to_string(),
+                    vec!["--release".into(), "--timeout".into(), "10".into()]
+                )]),
+            }
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(matches!(
+            Config::parse("yaer = 2023"),
+            Err(Error::Parser(_))
+        ));
+        assert!(matches!(
+            Config::parse("year = 2014"),
+            Err(Error::Parser(_))
+        ));
+
+        let config = Config::parse("[bench]\nmin_samples = 100\nmax_samples = 10").unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(Error::InvalidValue { key, .. }) if key == "bench.max_samples"
+        ));
+
+        let config = Config::parse("[defaults]\nsovle = [\"--release\"]").unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn environment_overrides_file() {
+        let mut config = Config::parse("year = 2022\nreadme = \"docs/README.md\"").unwrap();
+        config
+            .apply_overrides(|var| match var {
+                "AOC_YEAR" => Some("2023".into()),
+                "AOC_BENCH_MAX_SAMPLES" => Some("100".into()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.year, Some(year!(2023)));
+        assert_eq!(config.readme, "docs/README.md");
+        assert_eq!(config.bench.max_samples, 100);
+
+        let e = config.set("bench.budget", "soon").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "invalid `bench.budget`: expecting a number of seconds, got `soon`"
+        );
+        assert!(matches!(
+            config.set("budget", "1"),
+            Err(Error::UnknownSetting(_))
+        ));
+    }
+}
@@ -0,0 +1,312 @@
+/// Named examples of a day with their expected answers.
+/// Examples live in `data/{year}/examples/{day}.toml`, every `[[example]]` table holds an input and optional answers:
+///
+/// ```toml
+/// [[example]]
+/// name = "calibration"
+/// part_one = 142
+/// input = '''
+/// 1abc2
+/// pqr3stu8vwx
+/// '''
+/// ```
+///
+/// A test is generated for every example of a day, see [`crate::example_tests!`].
+use std::{fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::template::{
+    paths,
+    protocol::{PartRecord, PartStatus},
+    registry::Entry,
+};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse examples: {e}"),
+            Error::IO(e) => write!(f, "could not read examples: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// The expected answer of part one, the part is not checked against this example if missing.
+    #[serde(default, deserialize_with = "answer")]
+    pub part_one: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    pub part_two: Option<String>,
+}
+
+impl Example {
+    /// Renders the example as an `[[example]]` table, with the input as a multi-line literal string where possible.
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let mut out = String::from("[[example]]\n");
+        out += &format!("name = {}\n", toml::Value::String(self.name.clone()));
+
+        for (key, answer) in [("part_one", &self.part_one), ("part_two", &self.part_two)] {
+            match answer {
+                // numbers are only written as such if they read back as the same text, e.g. not `0042`.
+                Some(answer)
+                    if answer
+                        .parse::<i64>()
+                        .is_ok_and(|n| n.to_string() == *answer) =>
+                {
+                    out += &format!("{key} = {answer}\n");
+                }
+                Some(answer) => {
+                    out += &format!("{key} = {}\n", toml::Value::String(answer.clone()));
+                }
+                None => {}
+            }
+        }
+
+        if self.input.contains("'''") {
+            out += &format!("input = {}\n", toml::Value::String(self.input.clone()));
+        } else {
+            out += &format!("input = '''\n{}'''\n", self.input);
+        }
+
+        out
+    }
+}
+
+#[derive(Deserialize)]
+struct Examples {
+    #[serde(default)]
+    example: Vec<Example>,
+}
+
+/// Answers may be written as numbers or strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+fn answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(
+        Option::<Answer>::deserialize(deserializer)?.map(|answer| match answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => s,
+        }),
+    )
+}
+
+fn parse(s: &str) -> Result<Vec<Example>, Error> {
+    toml::from_str::<Examples>(s)
+        .map(|examples| examples.example)
+        .map_err(|e| Error::Parser(e.to_string()))
+}
+
+/// Reads the examples of a day, a missing file is treated as a day without examples.
+pub fn load(year: Year, day: Day) -> Result<Vec<Example>, Error> {
+    match fs::read_to_string(paths::examples_path(year, day)) {
+        Ok(s) => parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Adds examples to the examples file of a day, examples with the name of an existing example are skipped.
+/// Returns the names of the examples that were added.
+pub fn append(year: Year, day: Day, examples: &[Example]) -> Result<Vec<String>, Error> {
+    let path = paths::examples_path(year, day);
+    let mut contents = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let existing = parse(&contents)?;
+    let mut added = vec![];
+
+    for example in examples {
+        if existing.iter().any(|e| e.name == example.name) || added.contains(&example.name) {
+            continue;
+        }
+        if !contents.is_empty() {
+            contents += if contents.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+        }
+        contents += &example.to_toml();
+        added.push(example.name.clone());
+    }
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, contents)?;
+    Ok(added)
+}
+
+/// Runs a solution against the example `name` and asserts the answers it expects.
+/// Used by the tests that [`crate::example_tests!`] generates.
+pub fn check(solution: &Entry, name: &str) {
+    let (year, day) = (solution.year, solution.day);
+    let examples = load(year, day).unwrap_or_else(|e| panic!("{e}"));
+    let example = examples
+        .iter()
+        .find(|example| example.name == name)
+        .unwrap_or_else(|| {
+            panic!(
+                "example \"{name}\" not found in \"{}\"",
+                paths::examples_path(year, day)
+            )
+        });
+
+    let records = solution.run_input(&example.input, false, None);
+
+    // a failed parse step leaves no records of the parts, its outcome tells why.
+    if let Some(record) = records
+        .iter()
+        .find(|r| r.is_parse_step() && r.status != PartStatus::Solved)
+    {
+        panic!(
+            "example \"{name}\" could not be parsed: {}",
+            describe(record)
+        );
+    }
+
+    for (part, expected) in [(1, &example.part_one), (2, &example.part_two)] {
+        let Some(expected) = expected else {
+            continue;
+        };
+
+        let record = records
+            .iter()
+            .find(|r| r.part == part)
+            .expect("solutions have a record for each part");
+
+        assert_eq!(
+            record.answer.as_deref(),
+            Some(expected.as_str()),
+            "part {part} of example \"{name}\": {}",
+            describe(record)
+        );
+    }
+}
+
+fn describe(record: &PartRecord) -> String {
+    record
+        .outcome()
+        .map_or_else(|| "⏱ timeout".into(), |outcome| outcome.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Example};
+
+    #[test]
+    fn parses_examples() {
+        let examples = parse(
+            r#"
+[[example]]
+name = "first"
+part_one = 142
+input = '''
+1abc2
+'''
+
+[[example]]
+name = "second"
+part_two = "abc"
+input = "two1nine"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    name: "first".into(),
+                    input: "1abc2\n".into(),
+                    part_one: Some("142".into()),
+                    part_two: None,
+                },
+                Example {
+                    name: "second".into(),
+                    input: "two1nine".into(),
+                    part_one: None,
+                    part_two: Some("abc".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn roundtrips_through_toml() {
+        let examples = vec![
+            Example {
+                name: "part \"one\"".into(),
+                input: "1abc2\npqr3stu8vwx\n".into(),
+                part_one: Some("142".into()),
+                part_two: Some("abc".into()),
+            },
+            Example {
+                name: "quotes".into(),
+                input: "'''\n".into(),
+                part_one: None,
+                part_two: None,
+            },
+        ];
+
+        let toml = examples
+            .iter()
+            .map(Example::to_toml)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert!(toml.contains("input = '''\n1abc2\npqr3stu8vwx\n'''\n"));
+        assert_eq!(parse(&toml).unwrap(), examples);
+    }
+
+    #[test]
+    fn keeps_leading_zeros_of_answers() {
+        let example = Example {
+            name: "padded".into(),
+            input: "abc\n".into(),
+            part_one: Some("0042".into()),
+            part_two: Some("-7".into()),
+        };
+
+        let toml = example.to_toml();
+
+        assert!(toml.contains("part_one = \"0042\"\n"));
+        assert!(toml.contains("part_two = -7\n"));
+        assert_eq!(parse(&toml).unwrap(), vec![example]);
+    }
+
+    #[test]
+    fn treats_empty_file_as_no_examples() {
+        assert!(parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_examples_without_input() {
+        assert!(parse("[[example]]\nname = \"first\"\n").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+/// Selects and reads the input that a solution runs against.
+/// By default this is the downloaded puzzle input, `--input <path>`, `--stdin` and `--example` select other sources.
+use std::{
+    env,
+    fmt::Display,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::template::{config, examples, paths};
+use crate::{Day, Year};
+
+pub const INPUT_FLAG: &str = "--input";
+pub const STDIN_FLAG: &str = "--stdin";
+pub const EXAMPLE_FLAG: &str = "--example";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The downloaded puzzle input of the day.
+    #[default]
+    Puzzle,
+    /// The example file of the day, or its first named example.
+    Example,
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Missing { path: String, hint: String },
+    Empty { path: String, hint: String },
+    Arguments(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Missing { path, hint } => {
+                write!(f, "could not find input file \"{path}\". {hint}")
+            }
+            Error::Empty { path, hint } => write!(f, "input file \"{path}\" is empty. {hint}"),
+            Error::Arguments(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "could not read input: {e}"),
+        }
+    }
+}
+
+impl InputSource {
+    /// Parses the input source from command-line arguments.
+    pub fn from_args(args: &[String]) -> Result<Self, Error> {
+        let input_index = args.iter().position(|x| x == INPUT_FLAG);
+        let is_stdin = args.iter().any(|x| x == STDIN_FLAG);
+        let is_example = args.iter().any(|x| x == EXAMPLE_FLAG);
+
+        let source = match (input_index, is_stdin, is_example) {
+            (None, false, false) => InputSource::Puzzle,
+            (Some(index), false, false) => {
+                let path = args.get(index + 1).ok_or_else(|| {
+                    Error::Arguments("missing path, format: cargo solve 1 --input <path>".into())
+                })?;
+                InputSource::File(path.into())
+            }
+            (None, true, false) => InputSource::Stdin,
+            (None, false, true) => InputSource::Example,
+            _ => {
+                return Err(Error::Arguments(format!(
+                    "only one of `{INPUT_FLAG}`, `{STDIN_FLAG}` and `{EXAMPLE_FLAG}` can be used at a time."
+                )))
+            }
+        };
+
+        Ok(source)
+    }
+
+    /// The arguments that select this source in a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example => vec![EXAMPLE_FLAG.into()],
+            InputSource::File(path) => vec![INPUT_FLAG.into(), path.display().to_string()],
+            InputSource::Stdin => vec![STDIN_FLAG.into()],
+        }
+    }
+
+    /// Reads the input of a day, missing or empty files are reported with a hint on how to get them.
+    pub fn read(&self, year: Year, day: Day) -> Result<String, Error> {
+        let (path, hint) = match self {
+            InputSource::Stdin => return read_stdin(io::stdin()),
+            InputSource::Puzzle => (paths::input_path(year, day), download_hint(year, day)),
+            InputSource::Example => {
+                let path = paths::example_path(year, day);
+
+                // without an example file, the first named example of the day is used.
+                if !Path::new(&path).exists() {
+                    if let Some(example) = examples::load(year, day)
+                        .ok()
+                        .and_then(|examples| examples.into_iter().next())
+                    {
+                        return Ok(example.input);
+                    }
+                }
+
+                let hint = format!(
+                    "Copy the example of the puzzle description into it, or add one to \"{}\".",
+                    paths::examples_path(year, day)
+                );
+                (path, hint)
+            }
+            InputSource::File(path) => (
+                path.display().to_string(),
+                "Check the path passed to `--input`.".into(),
+            ),
+        };
+
+        read_path(path, hint)
+    }
+}
+
+/// Reads the input piped into the command, which is named `<stdin>` in errors.
+fn read_stdin(mut stdin: impl Read) -> Result<String, Error> {
+    let mut input = String::new();
+    stdin.read_to_string(&mut input)?;
+
+    if input.trim().is_empty() {
+        return Err(Error::Empty {
+            path: "<stdin>".into(),
+            hint: format!("Pipe the input into the command when using `{STDIN_FLAG}`."),
+        });
+    }
+
+    Ok(input)
+}
+
+fn read_path(path: String, hint: String) -> Result<String, Error> {
+    match std::fs::read_to_string(&path) {
+        Ok(input) if input.trim().is_empty() => Err(Error::Empty { path, hint }),
+        Ok(input) => Ok(input),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::Missing { path, hint }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn download_hint(year: Year, day: Day) -> String {
+    let day = day.into_inner();
+    if config::get().year == Some(year) {
+        format!("Run `cargo download {day}` to download it.")
+    } else {
+        format!("Run `cargo download {day} --year {year}` to download it.")
+    }
+}
+
+/// Reads the input selected by the arguments of the current process, exits with an error if it can't be read.
+#[must_use]
+pub fn read_input(year: Year, day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    match InputSource::from_args(&args).and_then(|source| source.read(year, day)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Whether the current process runs against an input other than the puzzle input.
+#[must_use]
+pub fn is_custom_input() -> bool {
+    env::args().any(|x| x == INPUT_FLAG || x == STDIN_FLAG || x == EXAMPLE_FLAG)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_path, read_stdin, Error, InputSource};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(
+            InputSource::from_args(&args("2023_01")).unwrap(),
+            InputSource::Puzzle
+        );
+        assert_eq!(
+            InputSource::from_args(&args("2023_01 --example")).unwrap(),
+            InputSource::Example
+        );
+        assert_eq!(
+            InputSource::from_args(&args("2023_01 --stdin")).unwrap(),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::from_args(&args("2023_01 --input foo.txt --time")).unwrap(),
+            InputSource::File("foo.txt".into())
+        );
+        assert!(InputSource::from_args(&args("2023_01 --input")).is_err());
+        assert!(InputSource::from_args(&args("2023_01 --stdin --example")).is_err());
+    }
+
+    #[test]
+    fn roundtrips_through_args() {
+        let source = InputSource::File("foo.txt".into());
+        let mut args = vec!["2023_01".to_string()];
+        args.extend(source.to_args());
+        assert_eq!(InputSource::from_args(&args).unwrap(), source);
+    }
+
+    #[test]
+    fn names_missing_paths() {
+        let err = read_path("data/2023/inputs/99.txt".into(), "hint".into()).unwrap_err();
+        assert!(matches!(err, Error::Missing { .. }));
+        assert_eq!(
+            err.to_string(),
+            "could not find input file \"data/2023/inputs/99.txt\". hint"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_inputs() {
+        let path = std::env::temp_dir().join(format!("aoc_empty_{}.txt", std::process::id()));
+        std::fs::write(&path, "\n").unwrap();
+
+        let err = read_path(path.display().to_string(), "hint".into()).unwrap_err();
+        assert!(matches!(err, Error::Empty { .. }));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_empty_stdin() {
+        let err = read_stdin(" \n".as_bytes()).unwrap_err();
+        assert!(matches!(err, Error::Empty { .. }));
+        assert!(err
+            .to_string()
+            .starts_with("input file \"<stdin>\" is empty."));
+
+        assert_eq!(read_stdin("1\n2\n".as_bytes()).unwrap(), "1\n2\n");
+    }
+}
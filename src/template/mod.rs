@@ -1,10 +1,28 @@
-use crate::Day;
+use crate::{Day, Year};
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_history;
+pub mod cli;
+pub mod clock;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod input;
+pub mod outcome;
+pub mod paths;
+pub mod protocol;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod solution;
+pub mod submissions;
+pub mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -12,25 +30,129 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd
+        .join(paths::data_dir(year, folder))
+        .join(format!("{day}.txt"));
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads the text file of one part to a string, e.g. `data/2023/examples/01-2.txt`.
+/// Useful for days that have a different example for each part.
+#[must_use]
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join(paths::data_dir(year, folder))
+        .join(format!("{day}-{part}.txt"));
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
+/// Creates the constants `YEAR`, `DAY` and `SOLUTION` and sets up the input and runner for each part.
+/// If no year is given, the year of the config is taken at compile time.
+/// A time budget for each part can be given in seconds, e.g. `solution!(2023, 5, timeout = 30)` or `timeout = 0.5`.
+/// Days that implement [`crate::Solution`] instead of free `part_one` and `part_two` functions pass their type, e.g. `solution!(2023, 5, Day05)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@entry $day:expr, $timeout:expr, $records:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// Entry of the current day in the solution registry.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Entry =
+            advent_of_code::template::registry::Entry {
+                year: YEAR,
+                day: DAY,
+                records: $records,
+                timeout: $timeout,
+            };
+    };
+    (@main $day:expr, $timeout:expr) => {
+        advent_of_code::solution!(@entry $day, $timeout, |input, is_timed, timeout| {
+            advent_of_code::template::runner::part_records(
+                part_one, part_two, input, YEAR, DAY, is_timed, timeout,
+            )
+        });
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = advent_of_code::template::input::read_input(YEAR, DAY);
+            run_part(part_one, &input, YEAR, DAY, 1, SOLUTION.timeout);
+            run_part(part_two, &input, YEAR, DAY, 2, SOLUTION.timeout);
         }
     };
+    (@trait $day:expr, $solution:ty, $timeout:expr) => {
+        advent_of_code::solution!(@entry $day, $timeout, |input, is_timed, timeout| {
+            advent_of_code::template::runner::solution_records::<$solution>(
+                input, YEAR, DAY, is_timed, timeout,
+            )
+        });
+
+        fn main() {
+            let input = advent_of_code::template::input::read_input(YEAR, DAY);
+            advent_of_code::template::runner::run_solution::<$solution>(
+                &input,
+                YEAR,
+                DAY,
+                SOLUTION.timeout,
+            );
+        }
+    };
+    ($year:expr, $day:expr, timeout = $secs:expr) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        // `Duration::from_secs_f64` can not be called in a constant, so the seconds are converted by hand.
+        advent_of_code::solution!(
+            @main $day,
+            Some(std::time::Duration::from_nanos(($secs as f64 * 1e9) as u64))
+        );
+    };
+    ($year:expr, $day:expr, $solution:ty, timeout = $secs:expr) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        advent_of_code::solution!(
+            @trait $day,
+            $solution,
+            Some(std::time::Duration::from_nanos(($secs as f64 * 1e9) as u64))
+        );
+    };
+    ($year:expr, $day:expr, $solution:ty) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        advent_of_code::solution!(@trait $day, $solution, None);
+    };
+    ($year:expr, $day:expr) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        advent_of_code::solution!(@main $day, None);
+    };
+    ($day:expr) => {
+        /// The year of the current day.
+        const YEAR: advent_of_code::Year =
+            advent_of_code::Year::__parse_unchecked(env!("AOC_YEAR"));
+
+        advent_of_code::solution!(@main $day, None);
+    };
+}
+
+/// Generates a test for every example in `data/{year}/examples/{day}.toml`.
+/// Meant to be used in the tests module of a solution, next to `use super::*;`.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/examples/",
+            env!("CARGO_BIN_NAME"),
+            ".rs"
+        ));
+    };
 }
@@ -0,0 +1,211 @@
+/// Outcomes of solution parts.
+/// Parts may return an [`Option`], where [`None`] means the part is not implemented yet, or a [`Result`] with an error that can be displayed.
+/// Panics of a part are caught and reported with their location, `todo!()` and `unimplemented!()` count as not implemented.
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// What one execution of a part came up with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(String),
+    Unimplemented,
+    Error(String),
+    Panic {
+        message: String,
+        /// Where the part panicked, e.g. `src/bin/2023_01.rs:12:5`.
+        location: Option<String>,
+    },
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Solved(answer) => write!(f, "{answer}"),
+            Outcome::Unimplemented => write!(f, "✖ not implemented"),
+            Outcome::Error(message) => write!(f, "✖ error: {message}"),
+            Outcome::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "✖ panicked at {location}: {message}"),
+            Outcome::Panic {
+                message,
+                location: None,
+            } => write!(f, "✖ panicked: {message}"),
+        }
+    }
+}
+
+/// Return types of solution parts.
+/// Parts may run on a separate thread, so their return types can not borrow.
+pub trait PartOutput: 'static {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl<T: Display + 'static> PartOutput for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        self.map_or(Outcome::Unimplemented, |answer| {
+            Outcome::Solved(answer.to_string())
+        })
+    }
+}
+
+impl<T: Display + 'static, E: Display + 'static> PartOutput for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Solved(answer.to_string()),
+            Err(e) => Outcome::Error(e.to_string()),
+        }
+    }
+}
+
+impl PartOutput for Outcome {
+    fn into_outcome(self) -> Outcome {
+        self
+    }
+}
+
+thread_local! {
+    /// Whether a panic on this thread is caught by [`catch`], its default report is silenced then.
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<(String, Option<String>)>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Installs a panic hook that records panics caught by [`catch`] and leaves all other panics to the previous hook.
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.get() {
+                let location = info.location().map(|location| {
+                    // the `advent_of_code` binary includes solutions by their absolute path.
+                    let location = location.to_string();
+                    match location.strip_prefix(env!("CARGO_MANIFEST_DIR")) {
+                        Some(relative) => relative.trim_start_matches(['/', '\\']).to_string(),
+                        None => location,
+                    }
+                });
+                LAST_PANIC.set(Some((panic_message(info.payload()), location)));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".into()
+    }
+}
+
+/// Whether a panic message is the one of `todo!()` or `unimplemented!()`, with or without a message of its own.
+/// Other panics that happen to start with these words, e.g. `"not implemented for negative numbers"`, are panics.
+fn is_unimplemented(message: &str) -> bool {
+    ["not yet implemented", "not implemented"]
+        .iter()
+        .any(|prefix| {
+            message
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(": "))
+        })
+}
+
+/// Runs `f` and turns a panic into an [`Outcome`].
+pub fn catch<R>(f: impl FnOnce() -> R) -> Result<R, Outcome> {
+    install_hook();
+
+    let was_catching = IS_CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IS_CATCHING.set(was_catching);
+
+    result.map_err(|payload| {
+        let (message, location) = LAST_PANIC
+            .take()
+            .unwrap_or_else(|| (panic_message(payload.as_ref()), None));
+
+        if is_unimplemented(&message) {
+            Outcome::Unimplemented
+        } else {
+            Outcome::Panic { message, location }
+        }
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{catch, Outcome, PartOutput};
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(42).into_outcome(), Outcome::Solved("42".into()));
+        assert_eq!(None::<u32>.into_outcome(), Outcome::Unimplemented);
+        assert_eq!(
+            Err::<u32, _>("bad digit in line 37").into_outcome(),
+            Outcome::Error("bad digit in line 37".into())
+        );
+    }
+
+    #[test]
+    fn catches_panics_with_location() {
+        let outcome = catch(|| -> Option<u32> { panic!("oops {}", 1) }).unwrap_err();
+        let Outcome::Panic { message, location } = outcome else {
+            panic!("expected a panic, got {outcome:?}");
+        };
+
+        assert_eq!(message, "oops 1");
+        assert!(location.unwrap().starts_with("src/template/outcome.rs:"));
+        assert_eq!(catch(|| 1), Ok(1));
+    }
+
+    #[test]
+    fn treats_todo_as_unimplemented() {
+        assert_eq!(catch(|| -> u32 { todo!() }), Err(Outcome::Unimplemented));
+        assert_eq!(
+            catch(|| -> u32 { unimplemented!("part two") }),
+            Err(Outcome::Unimplemented)
+        );
+    }
+
+    #[test]
+    fn reports_other_panics_about_implementations() {
+        let outcome = catch(|| -> u32 { panic!("not implemented for negative numbers") });
+        assert!(matches!(outcome, Err(Outcome::Panic { .. })));
+
+        let outcome = catch(|| -> u32 { panic!("not implemented yet") });
+        assert!(matches!(outcome, Err(Outcome::Panic { .. })));
+    }
+
+    #[test]
+    fn describes_outcomes() {
+        assert_eq!(Outcome::Error("bad".into()).to_string(), "✖ error: bad");
+        assert_eq!(
+            Outcome::Panic {
+                message: "oops".into(),
+                location: Some("src/bin/2023_01.rs:3:5".into())
+            }
+            .to_string(),
+            "✖ panicked at src/bin/2023_01.rs:3:5: oops"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+/// Locations of solutions and data files.
+/// Everything is scoped by year, so several years of puzzles can live in one workspace.
+/// Data files live below the `data_dir` of the [config](crate::template::config).
+use std::fs;
+
+use crate::template::config;
+use crate::{Day, Year};
+
+/// Scaffold templates of the project, e.g. `templates/grid.rs`.
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// The name of the solution binary of a day, e.g. `2023_01`.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    format!("{year}_{day}")
+}
+
+#[must_use]
+pub fn bin_path(year: Year, day: Day) -> String {
+    format!("src/bin/{}.rs", bin_name(year, day))
+}
+
+#[must_use]
+pub fn data_dir(year: Year, folder: &str) -> String {
+    format!("{}/{year}/{folder}", config::get().data_dir)
+}
+
+#[must_use]
+pub fn input_path(year: Year, day: Day) -> String {
+    format!("{}/{day}.txt", data_dir(year, "inputs"))
+}
+
+#[must_use]
+pub fn example_path(year: Year, day: Day) -> String {
+    format!("{}/{day}.txt", data_dir(year, "examples"))
+}
+
+/// The named examples of a day, see [`crate::template::examples`].
+#[must_use]
+pub fn examples_path(year: Year, day: Day) -> String {
+    format!("{}/{day}.toml", data_dir(year, "examples"))
+}
+
+#[must_use]
+pub fn puzzle_path(year: Year, day: Day) -> String {
+    format!("{}/{day}.md", data_dir(year, "puzzles"))
+}
+
+/// The answer ledger of a year.
+#[must_use]
+pub fn ledger_path(year: Year) -> String {
+    format!("{}/{year}/answers.toml", config::get().data_dir)
+}
+
+/// The benchmark runs of all machines, see [`crate::template::bench_history`].
+#[must_use]
+pub fn history_path() -> String {
+    format!("{}/benchmarks.jsonl", config::get().data_dir)
+}
+
+/// Answers that wait for a rate limit to pass, see [`crate::template::submissions`].
+#[must_use]
+pub fn queue_path() -> String {
+    format!("{}/pending.toml", config::get().data_dir)
+}
+
+/// A scaffold template of the project, see [`crate::template::commands::scaffold`].
+#[must_use]
+pub fn template_path(name: &str) -> String {
+    format!("{TEMPLATES_DIR}/{name}.rs")
+}
+
+/// The names of all scaffold templates of the project.
+#[must_use]
+pub fn template_names() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(TEMPLATES_DIR) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".rs").map(ToString::to_string)
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, input_path};
+    use crate::{day, year};
+
+    #[test]
+    fn scopes_paths_by_year() {
+        assert_eq!(bin_name(year!(2022), day!(5)), "2022_05");
+        assert_eq!(input_path(year!(2022), day!(5)), "data/2022/inputs/05.txt");
+    }
+}
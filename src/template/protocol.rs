@@ -0,0 +1,292 @@
+/// Machine-readable records that solution binaries emit for each part when running in JSON mode.
+/// Records are printed as one JSON object per line, so they can be told apart from other output of a solution.
+use std::{
+    env,
+    io::{self, Write},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{alloc::AllocStats, outcome::Outcome, runner::Stats};
+use crate::{Day, Year};
+
+pub const JSON_FLAG: &str = "--json";
+pub const JSON_ENV: &str = "AOC_JSON";
+
+/// The part number of the records of parse steps, see [`crate::Solution`].
+pub const PARSE_PART: u8 = 0;
+pub const PARSE_LABEL: &str = "Parse";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartStatus {
+    Solved,
+    Unimplemented,
+    /// The part returned an error.
+    Error,
+    Panic,
+    /// The part exceeded its time budget.
+    Timeout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: PartStatus,
+    pub timing: Stats,
+    /// Heap usage of the part, only measured with the `alloc_stats` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<AllocStats>,
+    /// The message of an error or panic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Where the part panicked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl PartRecord {
+    #[must_use]
+    pub fn new(year: Year, day: Day, part: u8, answer: Option<String>, timing: Stats) -> Self {
+        let outcome = answer.map_or(Outcome::Unimplemented, Outcome::Solved);
+        Self::from_outcome(year, day, part, outcome, timing)
+    }
+
+    #[must_use]
+    pub fn from_outcome(year: Year, day: Day, part: u8, outcome: Outcome, timing: Stats) -> Self {
+        let (status, answer, message, location) = match outcome {
+            Outcome::Solved(answer) => (PartStatus::Solved, Some(answer), None, None),
+            Outcome::Unimplemented => (PartStatus::Unimplemented, None, None, None),
+            Outcome::Error(message) => (PartStatus::Error, None, Some(message), None),
+            Outcome::Panic { message, location } => {
+                (PartStatus::Panic, None, Some(message), location)
+            }
+        };
+
+        Self {
+            year: year.into_inner(),
+            day: day.into_inner(),
+            part,
+            answer,
+            status,
+            timing,
+            memory: None,
+            message,
+            location,
+        }
+    }
+
+    /// The outcome of the part, [`None`] if it exceeded its time budget.
+    #[must_use]
+    pub fn outcome(&self) -> Option<Outcome> {
+        let message = self.message.clone().unwrap_or_default();
+
+        match self.status {
+            PartStatus::Solved => Some(Outcome::Solved(self.answer.clone().unwrap_or_default())),
+            PartStatus::Unimplemented => Some(Outcome::Unimplemented),
+            PartStatus::Error => Some(Outcome::Error(message)),
+            PartStatus::Panic => Some(Outcome::Panic {
+                message,
+                location: self.location.clone(),
+            }),
+            PartStatus::Timeout => None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_memory(mut self, memory: Option<AllocStats>) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// A parse step that succeeded, it has no answer.
+    #[must_use]
+    pub fn parse_step(year: Year, day: Day, timing: Stats) -> Self {
+        Self {
+            answer: None,
+            ..Self::from_outcome(
+                year,
+                day,
+                PARSE_PART,
+                Outcome::Solved(String::new()),
+                timing,
+            )
+        }
+    }
+
+    #[must_use]
+    pub fn is_parse_step(&self) -> bool {
+        self.part == PARSE_PART
+    }
+
+    /// How the part is labelled in output, e.g. `Part 1`.
+    #[must_use]
+    pub fn label(&self) -> String {
+        if self.is_parse_step() {
+            PARSE_LABEL.into()
+        } else {
+            format!("Part {}", self.part)
+        }
+    }
+
+    /// A part that exceeded its time budget of `timeout`.
+    #[must_use]
+    pub fn timeout(year: Year, day: Day, part: u8, timeout: Duration) -> Self {
+        Self {
+            year: year.into_inner(),
+            day: day.into_inner(),
+            part,
+            answer: None,
+            status: PartStatus::Timeout,
+            timing: Stats::single(timeout),
+            memory: None,
+            message: None,
+            location: None,
+        }
+    }
+
+    /// Parses a line of solution output, returns [`None`] for lines that are not records.
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records can always be serialized")
+    }
+}
+
+/// Whether the current process was asked to emit JSON records, either via `--json` or `AOC_JSON`.
+#[must_use]
+pub fn is_json_mode() -> bool {
+    env::args().any(|x| x == JSON_FLAG) || env::var_os(JSON_ENV).is_some()
+}
+
+/// Sends everything that is printed to stdout from now on to stderr and returns a writer for the original stdout.
+/// Solutions that run in-process print to the same stdout as the records, so this keeps JSON output parseable.
+#[cfg(unix)]
+pub fn take_stdout() -> io::Result<Box<dyn Write>> {
+    use std::os::fd::AsFd;
+
+    io::stdout().flush()?;
+    let stdout = io::stdout().as_fd().try_clone_to_owned()?;
+
+    // SAFETY: both descriptors are open, `dup2` only points stdout at the file of stderr.
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(Box::new(std::fs::File::from(stdout)))
+}
+
+/// Stdout can only be moved to stderr on unix, elsewhere output of solutions stays on stdout.
+#[cfg(not(unix))]
+pub fn take_stdout() -> io::Result<Box<dyn Write>> {
+    Ok(Box::new(io::stdout()))
+}
+
+/// (De)serializes a [`std::time::Duration`] as an integer amount of nanoseconds.
+pub(crate) mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        #[allow(clippy::cast_possible_truncation)]
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartRecord, PartStatus};
+    use crate::template::{alloc::AllocStats, outcome::Outcome, runner::Stats};
+    use crate::{day, year};
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord::new(
+            year!(2023),
+            day!(3),
+            2,
+            Some("(1, 2) @ 3 samples)".into()),
+            Stats::single(Duration::from_micros(15)),
+        );
+        let json = record.to_json();
+
+        assert!(!json.contains('\n'));
+        assert!(json.contains("\"median_ns\":15000"));
+        assert_eq!(PartRecord::parse(&json), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_memory_stats() {
+        let memory = AllocStats {
+            allocations: 3,
+            bytes: 1024,
+            peak_bytes: 512,
+        };
+        let record = PartRecord::new(year!(2023), day!(3), 1, None, Stats::single(Duration::ZERO))
+            .with_memory(Some(memory));
+        let json = record.to_json();
+
+        assert!(json.contains("\"peak_bytes\":512"));
+        assert_eq!(PartRecord::parse(&json).unwrap().memory, Some(memory));
+        assert!(
+            !PartRecord::new(year!(2023), day!(3), 1, None, Stats::single(Duration::ZERO))
+                .to_json()
+                .contains("memory")
+        );
+    }
+
+    #[test]
+    fn marks_missing_answers_as_unimplemented() {
+        let record = PartRecord::new(year!(2023), day!(3), 1, None, Stats::single(Duration::ZERO));
+        assert_eq!(record.status, PartStatus::Unimplemented);
+        assert!(record.to_json().contains("\"status\":\"unimplemented\""));
+    }
+
+    #[test]
+    fn roundtrips_panics() {
+        let outcome = Outcome::Panic {
+            message: "oops".into(),
+            location: Some("src/bin/2023_03.rs:12:5".into()),
+        };
+        let record = PartRecord::from_outcome(
+            year!(2023),
+            day!(3),
+            1,
+            outcome.clone(),
+            Stats::single(Duration::ZERO),
+        );
+        let parsed = PartRecord::parse(&record.to_json()).unwrap();
+
+        assert_eq!(parsed.status, PartStatus::Panic);
+        assert_eq!(parsed.outcome(), Some(outcome));
+    }
+
+    #[test]
+    fn marks_parts_over_budget_as_timeout() {
+        let record = PartRecord::timeout(year!(2023), day!(5), 2, Duration::from_secs(10));
+        assert_eq!(record.status, PartStatus::Timeout);
+        assert!(record.to_json().contains("\"status\":\"timeout\""));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartRecord::parse("Seeds: [79, 14, 55, 13]"), None);
+        assert_eq!(PartRecord::parse("{not json}"), None);
+    }
+}
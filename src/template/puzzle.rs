@@ -0,0 +1,162 @@
+/// Parses the puzzle descriptions that `cargo read` and `cargo download` save as markdown.
+/// Finds the code blocks of a description, along with the answers that are highlighted near them.
+use crate::template::examples::Example;
+
+/// A code block of a puzzle description, usually an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// The highlighted answer of part one following the block, if any.
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl CodeBlock {
+    #[must_use]
+    pub fn to_example(&self, name: &str) -> Example {
+        Example {
+            name: name.into(),
+            input: self.content.clone(),
+            part_one: self.part_one.clone(),
+            part_two: self.part_two.clone(),
+        }
+    }
+}
+
+/// Finds all code blocks of a puzzle description.
+///
+/// The answer of an example is the last highlighted value, e.g. `` `*142*` ``, between it and the next block of the same part.
+/// Part two often reuses the example of part one, so highlights in part two before its first block are attributed to the last block of part one.
+#[must_use]
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks: Vec<CodeBlock> = vec![];
+    let mut part = 1;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if let Some(content) = &mut block {
+            if line.trim_end() == "```" {
+                blocks.push(CodeBlock {
+                    part,
+                    content: block.take().unwrap_or_default(),
+                    part_one: None,
+                    part_two: None,
+                });
+            } else {
+                content.push_str(line);
+                content.push('\n');
+            }
+            continue;
+        }
+
+        if line.trim_end() == "```" {
+            block = Some(String::new());
+        } else if line.starts_with("## ") && line.contains("Part Two") {
+            part = 2;
+        } else if let Some(answer) = highlighted(line).pop() {
+            let Some(last) = blocks.last_mut() else {
+                continue;
+            };
+            match (part, last.part) {
+                (1, 1) => last.part_one = Some(answer),
+                (2, _) => last.part_two = Some(answer),
+                _ => {}
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Values in a line that are highlighted as code, e.g. `` `*142*` `` or `` *`142`* ``.
+fn highlighted(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    loop {
+        let start = match (rest.find("`*"), rest.find("*`")) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => break,
+        };
+        let closing = if &rest[start..start + 2] == "`*" {
+            "*`"
+        } else {
+            "`*"
+        };
+        rest = &rest[start + 2..];
+
+        let Some(len) = rest.find(closing) else {
+            break;
+        };
+        values.push(rest[..len].to_string());
+        rest = &rest[len + 2..];
+    }
+
+    values
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, highlighted, CodeBlock};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+The values of these lines are `12` and `38`. Adding these together produces `*142*`.
+
+## --- Part Two ---
+
+It looks like some of the digits are spelled out. For example:
+
+```
+two1nine
+```
+
+Adding these together produces `*281*`.
+";
+
+    fn block(part: u8, content: &str, part_one: Option<&str>, part_two: Option<&str>) -> CodeBlock {
+        CodeBlock {
+            part,
+            content: content.into(),
+            part_one: part_one.map(Into::into),
+            part_two: part_two.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn finds_blocks_and_answers() {
+        assert_eq!(
+            code_blocks(PUZZLE),
+            vec![
+                block(1, "1abc2\npqr3stu8vwx\n", Some("142"), None),
+                block(2, "two1nine\n", None, Some("281")),
+            ]
+        );
+    }
+
+    #[test]
+    fn attributes_reused_examples_to_part_one() {
+        let puzzle =
+            "```\n1 2\n```\n\nThe answer is `*3*`.\n\n## --- Part Two ---\n\nNow it is *`6`*.\n";
+        assert_eq!(
+            code_blocks(puzzle),
+            vec![block(1, "1 2\n", Some("3"), Some("6"))]
+        );
+    }
+
+    #[test]
+    fn finds_highlighted_values() {
+        assert_eq!(highlighted("a `*1*`, `2` and *`3`*"), vec!["1", "3"]);
+        assert!(highlighted("a *word* and `code`").is_empty());
+    }
+}
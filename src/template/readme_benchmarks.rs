@@ -1,11 +1,20 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path, process::Command, str::FromStr};
 
-use crate::Day;
+use itertools::Itertools;
+
+use crate::template::{
+    alloc, config, paths,
+    protocol::{PartRecord, PartStatus, PARSE_PART},
+};
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// The bar chart of the day totals, written next to the readme.
+pub const CHART_PATH: &str = "benchmarks.svg";
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -18,26 +27,119 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not update README: {e}"),
+            Error::IO(e) => write!(f, "could not access README: {e}"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Timings {
+    pub year: Year,
     pub day: Day,
+    /// Median time of the parse step, only set for solutions that implement [`crate::Solution`].
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Peak heap usage of both parts, only measured with the `alloc_stats` feature.
+    pub peak_memory: Option<String>,
     pub total_nanos: f64,
 }
 
+/// Optional columns of the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Median time of the parse step.
+    Parse,
+    /// Time of the parse step and both parts of a day.
+    Total,
+    /// Share of a day in the overall total.
+    Share,
+    /// Change of the day total compared to the table of the last commit.
+    Delta,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "parse" => Ok(Column::Parse),
+            "total" => Ok(Column::Total),
+            "share" => Ok(Column::Share),
+            "delta" => Ok(Column::Delta),
+            s => Err(format!(
+                "unknown column `{s}`, expected `parse`, `total`, `share` or `delta`"
+            )),
+        }
+    }
+}
+
+/// Parses a comma-separated list of columns, e.g. `parse,total`.
+pub fn parse_columns(s: &str) -> Result<Vec<Column>, String> {
+    s.split(',').map(str::parse).collect()
+}
+
+/// Order of the days within a year.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Day,
+    /// The slowest day first.
+    Cost,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "cost" => Ok(Sort::Cost),
+            s => Err(format!("unknown sort `{s}`, expected `day` or `cost`")),
+        }
+    }
+}
+
+impl Display for Sort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sort::Day => write!(f, "day"),
+            Sort::Cost => write!(f, "cost"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Whether to write a bar chart to [`CHART_PATH`] and link it below the table.
+    pub chart: bool,
+}
+
+impl TableOptions {
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{}", paths::bin_path(year, day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table between two occurences of `marker`, a single marker is replaced by the table.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,74 +160,418 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// Parses a duration as printed by the runner, e.g. `12.1µs`, into nanoseconds.
+fn parse_nanos(s: &str) -> Option<f64> {
+    let units = [
+        ("ns", 1.0),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ];
+    units.iter().find_map(|(unit, factor)| {
+        s.strip_suffix(unit)
+            .and_then(|value| value.parse::<f64>().ok())
+            .map(|value| value * factor)
+    })
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = std::time::Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+/// Reads the day totals of an existing table, keyed by the path of the day.
+/// Tables without a total column are summed up from their timing columns, failed parts are skipped.
+fn previous_totals(readme: &str) -> HashMap<String, f64> {
+    let Ok(position) = locate_table(readme, MARKER) else {
+        return HashMap::new();
+    };
+
+    let mut totals = HashMap::new();
+    let mut header: Vec<String> = vec![];
+
+    for line in readme[position.pos_start..position.pos_end].lines() {
+        let cells: Vec<&str> = line
+            .trim()
+            .trim_matches('|')
+            .split('|')
+            .map(str::trim)
+            .collect();
+
+        if cells.first() == Some(&"Day") {
+            header = cells.iter().map(ToString::to_string).collect();
+            continue;
+        }
+
+        let Some(path) = cells
+            .first()
+            .and_then(|cell| cell.split_once("]("))
+            .and_then(|(_, path)| path.strip_suffix(')'))
+        else {
+            continue;
+        };
+
+        let timing = |name: &str| {
+            header
+                .iter()
+                .position(|h| h == name)
+                .and_then(|i| cells.get(i))
+                .and_then(|cell| parse_nanos(cell.trim_matches('`')))
+        };
+
+        let total = timing("Total").or_else(|| {
+            let parts: Vec<f64> = ["Parse", "Part 1", "Part 2"]
+                .iter()
+                .filter_map(|name| timing(name))
+                .collect();
+            (!parts.is_empty()).then(|| parts.iter().sum())
+        });
+
+        if let Some(total) = total {
+            totals.insert(path.to_string(), total);
+        }
+    }
+
+    totals
+}
+
+fn format_delta(current: f64, previous: Option<f64>) -> String {
+    match previous {
+        None if current > 0.0 => "new".into(),
+        Some(previous) if current > 0.0 && previous > 0.0 => {
+            format!("{:+.1}%", (current - previous) / previous * 100.0)
+        }
+        _ => "-".into(),
+    }
+}
+
+/// Orders the timings of every year by `sort`, years stay in ascending order.
+fn sorted(mut timings: Vec<Timings>, sort: Sort) -> Vec<Timings> {
+    match sort {
+        Sort::Day => timings.sort_by_key(|t| (t.year, t.day)),
+        Sort::Cost => timings.sort_by(|a, b| {
+            a.year
+                .cmp(&b.year)
+                .then(b.total_nanos.total_cmp(&a.total_nanos))
+                .then(a.day.cmp(&b.day))
+        }),
+    }
+    timings
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+    previous: &HashMap<String, f64>,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
 
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
+    // the memory column is only shown if heap usage was measured.
+    let has_memory = timings.iter().any(|t| t.peak_memory.is_some());
+
+    let mut columns = vec![("Day", ":---:")];
+    if options.has(Column::Parse) {
+        columns.push(("Parse", ":---:"));
+    }
+    columns.extend([("Part 1", ":---:"), ("Part 2", ":---: ")]);
+    for (column, name) in [
+        (Column::Total, "Total"),
+        (Column::Share, "Share"),
+        (Column::Delta, "Delta"),
+    ] {
+        if options.has(column) {
+            columns.push((name, ":---:"));
+        }
+    }
+    if has_memory {
+        columns.push(("Peak memory", ":---:"));
+    }
+
+    let years = sorted(timings, options.sort)
+        .into_iter()
+        .group_by(|timing| timing.year);
+
+    for (year, timings) in &years {
+        lines.push(String::new());
+        lines.push(format!("{prefix}# {year}"));
+        lines.push(String::new());
+        lines.push(format!(
+            "| {} |",
+            columns.iter().map(|(name, _)| name).join(" | ")
+        ));
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "| {} |",
+            columns.iter().map(|(_, align)| align).join(" | ")
         ));
+
+        for timing in timings {
+            let path = get_path_for_bin(year, timing.day);
+            let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+            let mut cell = |value: Option<String>| {
+                cells.push(format!("`{}`", value.unwrap_or_else(|| "-".into())));
+            };
+
+            if options.has(Column::Parse) {
+                cell(timing.parse);
+            }
+            cell(timing.part_1);
+            cell(timing.part_2);
+            if options.has(Column::Total) {
+                cell((timing.total_nanos > 0.0).then(|| format_nanos(timing.total_nanos)));
+            }
+            if options.has(Column::Share) {
+                cell(
+                    (total_millis > 0.0)
+                        .then(|| format!("{:.1}%", timing.total_nanos / (total_millis * 1e4))),
+                );
+            }
+            if options.has(Column::Delta) {
+                cell(Some(format_delta(
+                    timing.total_nanos,
+                    previous.get(&path).copied(),
+                )));
+            }
+            if has_memory {
+                cell(timing.peak_memory);
+            }
+
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
     }
 
     lines.push(String::new());
+    if options.chart {
+        lines.push(format!("![Benchmarks](./{CHART_PATH})"));
+        lines.push(String::new());
+    }
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Renders a horizontal bar chart of the day totals in the order of the table.
+fn construct_chart(timings: &[Timings], sort: Sort) -> String {
+    const WIDTH: f64 = 640.0;
+    const LABEL_WIDTH: f64 = 110.0;
+    const BAR_WIDTH: f64 = 420.0;
+    const ROW_HEIGHT: f64 = 22.0;
+
+    let timings: Vec<Timings> = sorted(timings.to_vec(), sort)
+        .into_iter()
+        .filter(|t| t.total_nanos > 0.0)
+        .collect();
+    let max = timings.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW_HEIGHT * timings.len() as f64 + 10.0;
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">"#
+        ),
+        r##"<rect width="100%" height="100%" fill="#ffffff"/>"##.into(),
+    ];
+
+    for (i, timing) in timings.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = ROW_HEIGHT * i as f64 + 5.0;
+        let width = (timing.total_nanos / max * BAR_WIDTH).max(1.0);
+        let text_y = y + ROW_HEIGHT / 2.0 + 4.0;
+
+        svg.push(format!(
+            r#"<text x="{}" y="{text_y}" text-anchor="end">{} Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            timing.year,
+            timing.day.into_inner()
+        ));
+        svg.push(format!(
+            r##"<rect x="{LABEL_WIDTH}" y="{}" width="{width:.1}" height="{}" fill="#4c8bf5"/>"##,
+            y + 3.0,
+            ROW_HEIGHT - 6.0
+        ));
+        svg.push(format!(
+            r#"<text x="{:.1}" y="{text_y}">{}</text>"#,
+            LABEL_WIDTH + width + 6.0,
+            format_nanos(timing.total_nanos)
+        ));
+    }
+
+    svg.push("</svg>".into());
+    svg.push(String::new());
+    svg.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+    committed: Option<&str>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let previous = previous_totals(committed.unwrap_or(s));
+    let table = construct_table("##", timings, total_millis, options, &previous);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+/// The readme of the last commit, which the delta column compares with.
+fn committed_readme(path: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["show", &format!("HEAD:{path}")])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn update(
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
+    let path = &config::get().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+
+    if options.chart {
+        // the readme links the chart relative to itself.
+        let chart_path = Path::new(path).with_file_name(CHART_PATH);
+        fs::write(chart_path, construct_chart(&timings, options.sort))?;
+    }
+
+    // without a commit to compare with, the delta falls back to the table in the readme.
+    let committed = if options.has(Column::Delta) {
+        committed_readme(path)
+    } else {
+        None
+    };
+
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        options,
+        committed.as_deref(),
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/// Collects the median times of solved parts for the benchmark table.
+pub fn collect_timings(records: &[PartRecord], year: Year, day: Day) -> Timings {
+    let mut timings = Timings {
+        year,
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        peak_memory: None,
+        total_nanos: 0_f64,
+    };
+
+    let peaks: Vec<String> = records
+        .iter()
+        .filter_map(|r| r.memory.map(|m| alloc::format_bytes(m.peak_bytes)))
+        .collect();
+
+    if !peaks.is_empty() {
+        timings.peak_memory = Some(peaks.join(" / "));
+    }
+
+    records
+        .iter()
+        .filter(|r| r.status != PartStatus::Unimplemented)
+        .for_each(|record| {
+            let median = record.timing.median;
+
+            // parts that did not solve are listed, but not counted towards the total.
+            let timing_str = match record.status {
+                PartStatus::Timeout => Some("timeout".into()),
+                PartStatus::Error => Some("error".into()),
+                PartStatus::Panic => Some("panic".into()),
+                _ => {
+                    #[allow(clippy::cast_precision_loss)]
+                    {
+                        timings.total_nanos += median.as_nanos() as f64;
+                    }
+                    Some(format!("{median:.1?}"))
+                }
+            };
+
+            match record.part {
+                PARSE_PART => timings.parse = timing_str,
+                1 => timings.part_1 = timing_str,
+                2 => timings.part_2 = timing_str,
+                _ => {}
+            }
+        });
+
+    timings
+}
+
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{
+        collect_timings, construct_chart, parse_columns, update_content, Column, Sort,
+        TableOptions, Timings, MARKER,
+    };
+    use crate::template::{outcome::Outcome, protocol::PartRecord, runner::Stats};
+    use crate::{day, year};
+    use std::time::Duration;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
+                year: year!(2023),
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
+                peak_memory: None,
+                total_nanos: 3e+7,
             },
             Timings {
+                year: year!(2023),
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
+                peak_memory: None,
+                total_nanos: 7e+7,
             },
             Timings {
+                year: year!(2023),
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
+                peak_memory: None,
+                total_nanos: 9e+7,
             },
         ]
     }
@@ -134,28 +580,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            None,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            None,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            None,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            None,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            None,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,18 +644,27 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            None,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "### 2023",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023_04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +673,237 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn groups_benchmarks_by_year() {
+        let mut timings = get_mock_timings();
+        timings[0].year = year!(2022);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default(), None).unwrap();
+
+        assert!(s.contains("### 2022\n\n| Day | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2022_01.rs) | `10ms` | `20ms` |\n\n### 2023"));
+        assert_eq!(s.matches("| Day | Part 1 | Part 2 |").count(), 2);
+    }
+
+    #[test]
+    fn adds_memory_column_if_measured() {
+        let mut timings = get_mock_timings();
+        timings[0].peak_memory = Some("1.0 KiB / 2.0 KiB".into());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default(), None).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak memory |"));
+        assert!(
+            s.contains("| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` | `1.0 KiB / 2.0 KiB` |")
+        );
+        assert!(s.contains("| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` | `-` |"));
+    }
+
+    #[test]
+    fn adds_optional_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].parse = Some("5ms".into());
+        let options = TableOptions {
+            columns: parse_columns("parse,total,share,delta").unwrap(),
+            ..TableOptions::default()
+        };
+
+        let committed = [
+            MARKER,
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10ms` | `5ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `timeout` | `-` |",
+            MARKER,
+        ]
+        .join("\n");
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, timings, 190.0, &options, Some(&committed)).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Total | Share | Delta |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2023_01.rs) | `5ms` | `10ms` | `20ms` | `30.0ms` | `15.8%` | `+100.0%` |"
+        ));
+        assert!(s.contains(
+            "| [Day 2](./src/bin/2023_02.rs) | `-` | `30ms` | `40ms` | `70.0ms` | `36.8%` | `new` |"
+        ));
+    }
+
+    #[test]
+    fn compares_with_total_column() {
+        let options = TableOptions {
+            columns: vec![Column::Delta],
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, get_mock_timings(), 190.0, &options, None).unwrap();
+        let committed = s
+            .replace("`new`", "`-`")
+            .replace("`20ms` | `-` |", "`20ms` | `-` | `60.0ms` |");
+        let committed = committed.replace("| Delta |", "| Delta | Total |");
+
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &options,
+            Some(&committed),
+        )
+        .unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` | `-50.0%` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` | `+0.0%` |"));
+    }
+
+    #[test]
+    fn sorts_days_by_cost() {
+        let options = TableOptions {
+            sort: Sort::Cost,
+            chart: true,
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, get_mock_timings(), 190.0, &options, None).unwrap();
+
+        let day_4 = s.find("[Day 4]").unwrap();
+        let day_2 = s.find("[Day 2]").unwrap();
+        let day_1 = s.find("[Day 1]").unwrap();
+        assert!(day_4 < day_2 && day_2 < day_1);
+        assert!(s.contains("![Benchmarks](./benchmarks.svg)\n\n**Total: 190.00ms**"));
+    }
+
+    #[test]
+    fn draws_bar_chart() {
+        let chart = construct_chart(&get_mock_timings(), Sort::Cost);
+
+        assert!(chart.starts_with("<svg"));
+        assert_eq!(chart.matches("<rect").count(), 4);
+        assert!(chart.find("2023 Day 4").unwrap() < chart.find("2023 Day 1").unwrap());
+        assert!(chart.contains(">90.0ms</text>"));
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        assert!(parse_columns("total,average").is_err());
+    }
+
+    fn record(part: u8, answer: Option<&str>, nanos: u64) -> PartRecord {
+        PartRecord::new(
+            year!(2023),
+            day!(1),
+            part,
+            answer.map(Into::into),
+            Stats::single(Duration::from_nanos(nanos)),
+        )
+    }
+
+    #[test]
+    fn test_well_formed() {
+        let res = collect_timings(
+            &[record(1, Some("0"), 74), record(2, Some("10"), 74_130_000)],
+            year!(2023),
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
+    }
+
+    #[test]
+    fn test_parse_step() {
+        let res = collect_timings(
+            &[
+                PartRecord::parse_step(
+                    year!(2023),
+                    day!(1),
+                    Stats::single(Duration::from_nanos(26)),
+                ),
+                record(1, Some("0"), 74),
+                record(2, Some("10"), 100),
+            ],
+            year!(2023),
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 200_f64);
+        assert_eq!(res.parse.unwrap(), "26.0ns");
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+    }
+
+    #[test]
+    fn test_patterns_in_input() {
+        let res = collect_timings(
+            &[
+                record(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000),
+                record(2, Some("10s"), 100_000_000),
+            ],
+            year!(2023),
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 2100000000_f64);
+        assert_eq!(res.part_1.unwrap(), "2.0s");
+        assert_eq!(res.part_2.unwrap(), "100.0ms");
+    }
+
+    #[test]
+    fn test_missing_parts() {
+        let res = collect_timings(
+            &[record(1, None, 100), record(2, None, 100)],
+            year!(2023),
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
+
+    #[test]
+    fn test_timeout_parts() {
+        let res = collect_timings(
+            &[
+                record(1, Some("1"), 100),
+                PartRecord::timeout(year!(2023), day!(1), 2, Duration::from_secs(10)),
+            ],
+            year!(2023),
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 100_f64);
+        assert_eq!(res.part_1.unwrap(), "100.0ns");
+        assert_eq!(res.part_2.unwrap(), "timeout");
+    }
+
+    #[test]
+    fn test_failed_parts() {
+        let res = collect_timings(
+            &[
+                PartRecord::from_outcome(
+                    year!(2023),
+                    day!(1),
+                    1,
+                    Outcome::Error("bad line".into()),
+                    Stats::single(Duration::from_nanos(100)),
+                ),
+                PartRecord::from_outcome(
+                    year!(2023),
+                    day!(1),
+                    2,
+                    Outcome::Panic {
+                        message: "oops".into(),
+                        location: None,
+                    },
+                    Stats::single(Duration::from_nanos(100)),
+                ),
+            ],
+            year!(2023),
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.unwrap(), "error");
+        assert_eq!(res.part_2.unwrap(), "panic");
+    }
 }
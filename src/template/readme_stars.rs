@@ -0,0 +1,148 @@
+/// Module that fills the progress table of the readme with the stars of accepted answers.
+/// The table matches the one of the `advent-readme-stars` action, but is built offline from the answer ledgers.
+use std::fs;
+
+use crate::template::{
+    answers::Ledger,
+    config,
+    readme_benchmarks::{locate_table, Error},
+};
+use crate::{all_days, Day, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+const STAR: &str = "⭐";
+
+/// The stars of both parts of every day with at least one accepted answer.
+fn stars(ledger: &Ledger) -> Vec<(Day, [bool; 2])> {
+    all_days()
+        .map(|day| (day, [1, 2].map(|part| ledger.accepted(day, part).is_some())))
+        .filter(|(_, stars)| stars.contains(&true))
+        .collect()
+}
+
+/// Lists every day with at least one accepted answer, years without stars are left out.
+fn construct_table(prefix: &str, ledgers: &[(Year, Ledger)]) -> String {
+    let mut lines: Vec<String> = vec![MARKER.into()];
+
+    for (year, ledger) in ledgers {
+        let days = stars(ledger);
+
+        if days.is_empty() {
+            continue;
+        }
+
+        lines.push(format!("{prefix} {year} Results"));
+        lines.push(String::new());
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: |".into());
+
+        for (day, stars) in days {
+            let [part_1, part_2] = stars.map(|star| if star { STAR } else { " " });
+            lines.push(format!(
+                "| [Day {}](https://adventofcode.com/{year}/day/{}) | {part_1} | {part_2} |",
+                day.into_inner(),
+                day.into_inner()
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, ledgers: &[(Year, Ledger)]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", ledgers);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Replaces the progress table of the readme with the stars of the ledgers.
+/// Returns the number of stars in the table.
+pub fn update(ledgers: &[(Year, Ledger)]) -> Result<usize, Error> {
+    let path = &config::get().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, ledgers)?;
+    fs::write(path, &readme)?;
+
+    Ok(ledgers
+        .iter()
+        .flat_map(|(_, ledger)| stars(ledger))
+        .flat_map(|(_, stars)| stars)
+        .filter(|&star| star)
+        .count())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::template::{answers::Ledger, aoc_client::SubmissionOutcome};
+    use crate::{day, year};
+
+    fn ledger(days: &[(crate::Day, u8)]) -> Ledger {
+        let mut ledger = Ledger::default();
+        for &(day, part) in days {
+            ledger.record(day, part, "42", &SubmissionOutcome::Correct);
+        }
+        ledger.record(
+            day!(3),
+            1,
+            "1",
+            &SubmissionOutcome::Incorrect { hint: None },
+        );
+        ledger
+    }
+
+    #[test]
+    fn fills_table_with_accepted_answers() {
+        let mut s = format!("# readme\n{MARKER}\n\nbody");
+        let ledgers = [(
+            year!(2023),
+            ledger(&[(day!(1), 1), (day!(1), 2), (day!(2), 1)]),
+        )];
+        update_content(&mut s, &ledgers).unwrap();
+
+        let expected = [
+            "# readme",
+            MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "",
+            MARKER,
+            "",
+            "body",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn replaces_existing_table() {
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, &[(year!(2022), ledger(&[(day!(25), 1)]))]).unwrap();
+        update_content(
+            &mut s,
+            &[
+                (year!(2022), ledger(&[(day!(25), 1)])),
+                (year!(2023), ledger(&[])),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2022 Results").count(), 1);
+        assert!(!s.contains("2023"));
+    }
+
+    #[test]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        assert!(update_content(&mut s, &[]).is_err());
+    }
+}
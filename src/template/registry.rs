@@ -0,0 +1,147 @@
+/// Registry of solutions that are compiled into the same binary as the commands that run them.
+/// Every solution declared with [`crate::solution!`] exposes an [`Entry`] as `SOLUTION`.
+/// The runner binary collects these entries from `src/bin` at build time, so all days can run in one process.
+/// It is separate from the `advent_of_code` binary, so a solution that does not compile only breaks `all` and `verify`.
+use std::{ffi::OsString, process, time::Duration};
+
+use crate::template::{
+    alloc,
+    commands::solve,
+    input::{self, InputSource},
+    protocol::PartRecord,
+};
+use crate::{Day, Year};
+
+/// The binary that the solutions are compiled into.
+pub const RUNNER_BIN: &str = "advent_of_code_runner";
+
+/// Runs a command in the runner binary and exits with its status.
+/// The runner is built with the profile and features of the current binary, e.g. with optimizations for `cargo all`.
+pub fn run_in_runner(args: impl IntoIterator<Item = OsString>) -> ! {
+    let mut cmd = solve::cargo();
+    cmd.args(["run", "--quiet", "--bin", RUNNER_BIN]);
+
+    if !cfg!(debug_assertions) {
+        cmd.arg("--release");
+    }
+
+    if alloc::is_enabled() {
+        cmd.args(["--features", "alloc_stats"]);
+    }
+
+    let status = cmd.arg("--").args(args).status().unwrap_or_else(|e| {
+        eprintln!("Failed to run {RUNNER_BIN}: {e}");
+        process::exit(1);
+    });
+    process::exit(status.code().unwrap_or(1));
+}
+
+/// Runs all steps of a solution against an input and collects their records.
+/// Arguments are the input, whether to benchmark and the time budget of each step.
+pub type Records = fn(&str, bool, Option<Duration>) -> Vec<PartRecord>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub year: Year,
+    pub day: Day,
+    pub records: Records,
+    /// The time budget of each part, if the day declares one.
+    pub timeout: Option<Duration>,
+}
+
+impl Entry {
+    /// Runs the solution against the input of the day and collects its records.
+    /// A given `timeout` replaces the time budget of the day.
+    pub fn run(
+        &self,
+        is_timed: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<PartRecord>, input::Error> {
+        let input = InputSource::Puzzle.read(self.year, self.day)?;
+        Ok(self.run_input(&input, is_timed, timeout))
+    }
+
+    /// Runs the solution against `input`, a given `timeout` replaces the time budget of the day.
+    #[must_use]
+    pub fn run_input(
+        &self,
+        input: &str,
+        is_timed: bool,
+        timeout: Option<Duration>,
+    ) -> Vec<PartRecord> {
+        (self.records)(input, is_timed, timeout.or(self.timeout))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    #[must_use]
+    pub fn new(entries: impl IntoIterator<Item = Entry>) -> Self {
+        let mut entries: Vec<Entry> = entries.into_iter().collect();
+        entries.sort_by_key(|s| (s.year, s.day));
+        Self { entries }
+    }
+
+    #[must_use]
+    pub fn get(&self, year: Year, day: Day) -> Option<&Entry> {
+        self.entries.iter().find(|s| s.year == year && s.day == day)
+    }
+
+    /// All years with at least one registered solution, in ascending order.
+    #[must_use]
+    pub fn years(&self) -> Vec<Year> {
+        let mut years: Vec<Year> = self.entries.iter().map(|s| s.year).collect();
+        years.dedup();
+        years
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Entry, Registry};
+    use crate::template::runner::part_records;
+    use crate::{day, year};
+
+    fn entry(year: crate::Year, day: crate::Day) -> Entry {
+        Entry {
+            year,
+            day,
+            records: |input, is_timed, timeout| {
+                part_records(
+                    |input: &str| Some(input.len()),
+                    |_: &str| None::<u32>,
+                    input,
+                    year!(2022),
+                    day!(1),
+                    is_timed,
+                    timeout,
+                )
+            },
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn finds_registered_solutions() {
+        let registry = Registry::new([
+            entry(year!(2023), day!(2)),
+            entry(year!(2022), day!(1)),
+            entry(year!(2023), day!(1)),
+        ]);
+
+        assert_eq!(registry.years(), vec![year!(2022), year!(2023)]);
+        assert!(registry.get(year!(2023), day!(2)).is_some());
+        assert!(registry.get(year!(2022), day!(2)).is_none());
+
+        let records = registry
+            .get(year!(2022), day!(1))
+            .unwrap()
+            .run_input("abc", false, None);
+        assert_eq!(records[0].answer, Some("3".into()));
+        assert_eq!(records[1].answer, None);
+    }
+}
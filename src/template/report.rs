@@ -0,0 +1,391 @@
+/// Module that renders a self-contained HTML report of the progress and performance of all days.
+/// The report is built from recorded data only: accepted answers of the ledgers and runs of the benchmark history.
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::template::{
+    answers::Ledger,
+    bench_history::HistoryEntry,
+    outcome::Outcome,
+    protocol::{PartRecord, PartStatus, PARSE_PART},
+    readme_benchmarks::{collect_timings, get_path_for_bin, Timings},
+};
+use crate::{Day, Year};
+
+pub const REPORT_PATH: &str = "report.html";
+
+/// Shown instead of answers in redacted reports.
+const REDACTED: &str = "•••••";
+
+/// Everything that is known about one day.
+pub struct DayReport {
+    pub timings: Timings,
+    /// Records of the day in the latest benchmark run that included it, empty if the day was never benchmarked.
+    pub records: Vec<PartRecord>,
+    /// Accepted answers of both parts.
+    pub accepted: [Option<String>; 2],
+    /// Total time of the day in every benchmark run, oldest first.
+    pub history: Vec<f64>,
+}
+
+/// Collects the reports of `days` from the ledgers of their years and the runs of `history`.
+#[must_use]
+pub fn collect(
+    days: &[(Year, Day)],
+    ledgers: &BTreeMap<Year, Ledger>,
+    history: &[&HistoryEntry],
+) -> Vec<DayReport> {
+    let records_of = |entry: &HistoryEntry, year: Year, day: Day| -> Vec<PartRecord> {
+        entry
+            .records
+            .iter()
+            .filter(|r| r.year == year.into_inner() && r.day == day.into_inner())
+            .cloned()
+            .collect()
+    };
+
+    days.iter()
+        .map(|&(year, day)| {
+            // later runs may only have benchmarked other days or years.
+            let records = history
+                .iter()
+                .rev()
+                .map(|entry| records_of(entry, year, day))
+                .find(|records| !records.is_empty())
+                .unwrap_or_default();
+
+            let history = history
+                .iter()
+                .map(|entry| collect_timings(&records_of(entry, year, day), year, day))
+                .filter(|timings| timings.total_nanos > 0.0)
+                .map(|timings| timings.total_nanos)
+                .collect();
+
+            let accepted = [1, 2].map(|part| {
+                ledgers
+                    .get(&year)
+                    .and_then(|ledger| ledger.accepted(day, part))
+                    .map(Into::into)
+            });
+
+            DayReport {
+                timings: collect_timings(&records, year, day),
+                records,
+                accepted,
+                history,
+            }
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The status of a part, as a CSS class and a label.
+fn status(record: Option<&PartRecord>, accepted: Option<&str>) -> (&'static str, String) {
+    let Some(record) = record else {
+        return if accepted.is_some() {
+            ("accepted", "★ accepted".into())
+        } else {
+            ("missing", "not run".into())
+        };
+    };
+
+    match (record.outcome(), accepted) {
+        (Some(Outcome::Solved(answer)), Some(accepted)) if answer == accepted => {
+            ("accepted", "★ accepted".into())
+        }
+        (Some(Outcome::Solved(_)), Some(_)) => ("failed", "✖ wrong answer".into()),
+        (Some(Outcome::Solved(_)), None) => ("solved", "solved".into()),
+        (Some(Outcome::Unimplemented), _) => ("missing", "not implemented".into()),
+        (Some(outcome), _) => ("failed", escape(&outcome.to_string())),
+        (None, _) => ("failed", "⏱ timeout".into()),
+    }
+}
+
+/// The median time of a part with the other statistics of the run as tooltip.
+fn timing_cell(record: Option<&PartRecord>) -> String {
+    match record {
+        Some(record) if record.status == PartStatus::Solved => {
+            let t = &record.timing;
+            format!(
+                r#"<td class="timing" title="mean {:.1?}, min {:.1?}, p95 {:.1?}, ±{:.1?} @ {} samples">{:.1?}</td>"#,
+                t.mean, t.min, t.p95, t.std_dev, t.samples, t.median
+            )
+        }
+        _ => r#"<td class="timing">-</td>"#.into(),
+    }
+}
+
+/// Renders the day totals of the benchmark history as an inline line chart.
+fn sparkline(history: &[f64]) -> String {
+    const WIDTH: f64 = 120.0;
+    const HEIGHT: f64 = 24.0;
+
+    if history.len() < 2 {
+        return "-".into();
+    }
+
+    let min = history.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = history.iter().copied().fold(0.0, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+
+    #[allow(clippy::cast_precision_loss)]
+    let step = WIDTH / (history.len() - 1) as f64;
+
+    let points = history
+        .iter()
+        .enumerate()
+        .map(|(i, nanos)| {
+            #[allow(clippy::cast_precision_loss)]
+            let x = step * i as f64;
+            let y = HEIGHT - 2.0 - (nanos - min) / range * (HEIGHT - 4.0);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        r#"<svg width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}"><polyline points="{points}" fill="none" stroke="currentColor" stroke-width="1.5"/></svg>"#
+    )
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+fn render_day(report: &DayReport, redact: bool) -> String {
+    let timings = &report.timings;
+    let record = |part: u8| report.records.iter().find(|r| r.part == part);
+
+    let mut cells = vec![format!(
+        r#"<td><a href="{}">Day {}</a></td>"#,
+        get_path_for_bin(timings.year, timings.day),
+        timings.day.into_inner()
+    )];
+
+    for part in [1, 2] {
+        let accepted = report.accepted[usize::from(part - 1)].as_deref();
+        let (class, label) = status(record(part), accepted);
+        cells.push(format!(r#"<td class="{class}">{label}</td>"#));
+
+        let answer = match accepted {
+            Some(_) if redact => REDACTED.into(),
+            Some(answer) => format!("<code>{}</code>", escape(answer)),
+            None => "-".into(),
+        };
+        cells.push(format!("<td>{answer}</td>"));
+    }
+
+    cells.push(timing_cell(record(PARSE_PART)));
+    cells.push(timing_cell(record(1)));
+    cells.push(timing_cell(record(2)));
+
+    let total = if timings.total_nanos > 0.0 {
+        format_nanos(timings.total_nanos)
+    } else {
+        "-".into()
+    };
+    cells.push(format!(r#"<td class="timing">{total}</td>"#));
+    cells.push(format!(
+        r#"<td class="history">{}</td>"#,
+        sparkline(&report.history)
+    ));
+
+    format!("<tr>{}</tr>", cells.join(""))
+}
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 72em; color: #1f2328; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2em; }
+th, td { padding: 0.4em 0.6em; border-bottom: 1px solid #d0d7de; text-align: left; }
+th { background: #f6f8fa; }
+.timing { text-align: right; font-variant-numeric: tabular-nums; }
+.accepted { color: #9a6700; }
+.solved { color: #1a7f37; }
+.failed { color: #cf222e; }
+.missing { color: #6e7781; }
+.history { color: #0969da; }
+";
+
+/// Renders the report as a self-contained HTML document, answers are hidden if `redact` is set.
+/// `summary` describes the benchmark run the timings are taken from.
+#[must_use]
+pub fn render(reports: &[DayReport], summary: &str, redact: bool) -> String {
+    let mut html = vec![
+        "<!DOCTYPE html>".into(),
+        r#"<html lang="en">"#.into(),
+        "<head>".into(),
+        r#"<meta charset="utf-8">"#.into(),
+        "<title>Advent of Code</title>".into(),
+        format!("<style>{STYLE}</style>"),
+        "</head>".into(),
+        "<body>".into(),
+        "<h1>Advent of Code</h1>".into(),
+        format!("<p>{}</p>", escape(summary)),
+    ];
+
+    let mut years: BTreeMap<Year, Vec<&DayReport>> = BTreeMap::new();
+    for report in reports {
+        years.entry(report.timings.year).or_default().push(report);
+    }
+
+    for (year, reports) in years {
+        let stars = reports
+            .iter()
+            .flat_map(|r| &r.accepted)
+            .filter(|a| a.is_some())
+            .count();
+        let total = reports.iter().map(|r| r.timings.total_nanos).sum::<f64>();
+
+        html.push(format!("<h2>{year}</h2>"));
+        html.push(format!(
+            "<p>{stars} ★ &middot; total {}</p>",
+            format_nanos(total)
+        ));
+        html.push("<table>".into());
+        html.push(
+            "<tr><th>Day</th><th>Part 1</th><th>Answer 1</th><th>Part 2</th><th>Answer 2</th><th>Parse</th><th>Time 1</th><th>Time 2</th><th>Total</th><th>History</th></tr>"
+                .into(),
+        );
+        for report in reports {
+            html.push(render_day(report, redact));
+        }
+        html.push("</table>".into());
+    }
+
+    html.push("</body>".into());
+    html.push("</html>".into());
+    html.push(String::new());
+    html.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use super::{collect, render, sparkline};
+    use crate::template::{
+        answers::Ledger, aoc_client::SubmissionOutcome, bench_history::HistoryEntry,
+        outcome::Outcome, protocol::PartRecord, runner::Stats,
+    };
+    use crate::{day, year};
+
+    fn record(day: u8, part: u8, outcome: Outcome, nanos: u64) -> PartRecord {
+        PartRecord::from_outcome(
+            year!(2023),
+            crate::Day::new(day).unwrap(),
+            part,
+            outcome,
+            Stats::single(Duration::from_nanos(nanos)),
+        )
+    }
+
+    fn entry(records: Vec<PartRecord>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 0,
+            revision: None,
+            machine: "test".into(),
+            records,
+        }
+    }
+
+    fn fixture() -> (Vec<HistoryEntry>, BTreeMap<crate::Year, Ledger>) {
+        let history = vec![
+            entry(vec![record(1, 1, Outcome::Solved("42".into()), 2_000)]),
+            entry(vec![
+                record(1, 1, Outcome::Solved("42".into()), 1_000),
+                record(1, 2, Outcome::Error("bad <input>".into()), 10),
+                record(2, 1, Outcome::Solved("7".into()), 500),
+            ]),
+        ];
+
+        let mut ledger = Ledger::default();
+        ledger.record(day!(1), 1, "42", &SubmissionOutcome::Correct);
+        ledger.record(day!(2), 1, "8", &SubmissionOutcome::Correct);
+
+        (history, BTreeMap::from([(year!(2023), ledger)]))
+    }
+
+    #[test]
+    fn collects_latest_run_and_history() {
+        let (history, ledgers) = fixture();
+        let history: Vec<&HistoryEntry> = history.iter().collect();
+        let reports = collect(
+            &[(year!(2023), day!(1)), (year!(2023), day!(3))],
+            &ledgers,
+            &history,
+        );
+
+        assert_eq!(reports[0].records.len(), 2);
+        assert_eq!(reports[0].accepted, [Some("42".into()), None]);
+        assert_eq!(reports[0].history, vec![2_000.0, 1_000.0]);
+        assert!(reports[1].records.is_empty());
+        assert!(reports[1].history.is_empty());
+    }
+
+    #[test]
+    fn collects_latest_run_of_each_day() {
+        let (mut history, ledgers) = fixture();
+        history.push(entry(vec![PartRecord::from_outcome(
+            year!(2024),
+            day!(1),
+            1,
+            Outcome::Solved("9".into()),
+            Stats::single(Duration::from_nanos(100)),
+        )]));
+        let history: Vec<&HistoryEntry> = history.iter().collect();
+        let reports = collect(
+            &[(year!(2023), day!(1)), (year!(2024), day!(1))],
+            &ledgers,
+            &history,
+        );
+
+        assert_eq!(reports[0].records.len(), 2);
+        assert_eq!(reports[0].records[0].answer.as_deref(), Some("42"));
+        assert_eq!(reports[1].records.len(), 1);
+        assert_eq!(reports[1].records[0].answer.as_deref(), Some("9"));
+    }
+
+    #[test]
+    fn renders_statuses_and_answers() {
+        let (history, ledgers) = fixture();
+        let history: Vec<&HistoryEntry> = history.iter().collect();
+        let reports = collect(
+            &[(year!(2023), day!(1)), (year!(2023), day!(2))],
+            &ledgers,
+            &history,
+        );
+        let html = render(&reports, "latest run", false);
+
+        assert!(html.contains(r#"<a href="./src/bin/2023_01.rs">Day 1</a>"#));
+        assert!(html.contains(r#"<td class="accepted">★ accepted</td><td><code>42</code></td>"#));
+        assert!(html.contains("✖ error: bad &lt;input&gt;"));
+        assert!(html.contains(r#"<td class="failed">✖ wrong answer</td><td><code>8</code></td>"#));
+        assert!(html.contains("<polyline"));
+        assert!(html.contains("<p>2 ★ &middot; total 1.5µs</p>"));
+        assert!(!html.contains("http"));
+    }
+
+    #[test]
+    fn redacts_answers() {
+        let (history, ledgers) = fixture();
+        let history: Vec<&HistoryEntry> = history.iter().collect();
+        let reports = collect(&[(year!(2023), day!(1))], &ledgers, &history);
+        let html = render(&reports, "", true);
+
+        assert!(!html.contains("<code>42</code>"));
+        assert!(html.contains("•••••"));
+    }
+
+    #[test]
+    fn skips_sparkline_without_history() {
+        assert_eq!(sparkline(&[1.0]), "-");
+        assert!(sparkline(&[1.0, 1.0]).contains(r#"points="0.0,22.0 120.0,22.0""#));
+    }
+}
@@ -1,102 +1,523 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::Ledger;
+use crate::template::aoc_client::{AocClient, SubmissionOutcome};
+use crate::template::clock::SystemClock;
+use crate::template::outcome::{self, Outcome, PartOutput};
+use crate::template::protocol::{self, PartRecord, PARSE_LABEL, PARSE_PART};
+use crate::template::solution::Solution;
+use crate::template::submissions::{self, Pending};
+use crate::template::{config, input, paths, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::{
+    mpsc::{self, RecvTimeoutError},
+    Arc,
+};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Flag that sets the time budget of each part in seconds, overriding the budget of the day.
+pub const TIMEOUT_FLAG: &str = "--timeout";
+
+/// Stack size of threads that run solution parts, matching the main thread so recursive solutions behave the same.
+pub(crate) const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+pub fn run_part<R: PartOutput>(
+    func: impl Fn(&str) -> R + Copy + Send + 'static,
+    input: &str,
+    year: Year,
+    day: Day,
+    part: u8,
+    timeout: Option<Duration>,
+) {
+    let is_timed = env::args().any(|x| x == "--time");
+    let timeout = timeout_arg().or(timeout);
+    let input: Arc<str> = Arc::from(input);
+
+    if protocol::is_json_mode() {
+        println!(
+            "{}",
+            run_record(func, &input, year, day, part, is_timed, timeout).to_json()
+        );
+        return;
+    }
+
+    print_part(func, &input, year, day, part, is_timed, timeout);
+}
+
+/// Runs the parse step and both parts of a [`Solution`] against the same parsed input.
+pub fn run_solution<S: Solution>(input: &str, year: Year, day: Day, timeout: Option<Duration>) {
+    let is_timed = env::args().any(|x| x == "--time");
+    let timeout = timeout_arg().or(timeout);
+
+    if protocol::is_json_mode() {
+        for record in solution_records::<S>(input, year, day, is_timed, timeout) {
+            println!("{}", record.to_json());
+        }
+        return;
+    }
+
+    if is_timed {
+        print!("{PARSE_LABEL}: > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let (parsed, record) = parse_record::<S>(input, year, day, is_timed, timeout);
+
+    match record.outcome() {
+        None => print_timeout(PARSE_LABEL, timeout.unwrap_or_default()),
+        Some(Outcome::Solved(_)) => {
+            print!("\r");
+            println!(
+                "{PARSE_LABEL}:{}{}",
+                format_duration(&record.timing),
+                format_memory(record.memory.as_ref())
+            );
+        }
+        Some(outcome) => print_result(&outcome, PARSE_LABEL, &format_duration(&record.timing)),
+    }
+
+    let Some(parsed) = parsed else {
+        return;
+    };
+
+    print_part(S::part_one, &parsed, year, day, 1, is_timed, timeout);
+    print_part(S::part_two, &parsed, year, day, 2, is_timed, timeout);
+}
+
+/// Runs one part, prints its result and submits it if requested.
+fn print_part<I: ?Sized + Send + Sync + 'static, R: PartOutput>(
+    func: impl Fn(&I) -> R + Copy + Send + 'static,
+    input: &Arc<I>,
+    year: Year,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let run = run_timed(func, input, is_timed, timeout, |result| {
+        print_result(result, &part_str, "");
+        if is_timed && result.answer().is_some() {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    });
+
+    let Some((result, stats, memory)) = run else {
+        print_timeout(&part_str, timeout.unwrap_or_default());
+        return;
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let stats_str = format_duration(&stats) + &format_memory(memory.as_ref());
+    print_result(&result, &part_str, &stats_str);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(answer) = result.answer() {
+        submit_result(answer, year, day, part);
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+/// Runs a solution part without printing and returns its record.
+pub fn run_record<I: ?Sized + Send + Sync + 'static, R: PartOutput>(
+    func: impl Fn(&I) -> R + Copy + Send + 'static,
+    input: &Arc<I>,
+    year: Year,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> PartRecord {
+    match run_timed(func, input, is_timed, timeout, |_| {}) {
+        Some((result, stats, memory)) => {
+            PartRecord::from_outcome(year, day, part, result, stats).with_memory(memory)
+        }
+        None => PartRecord::timeout(year, day, part, timeout.unwrap_or_default()),
+    }
+}
+
+/// Runs both parts of a day that declares free `part_one` and `part_two` functions and returns their records.
+pub fn part_records<A: PartOutput, B: PartOutput>(
+    part_one: impl Fn(&str) -> A + Copy + Send + 'static,
+    part_two: impl Fn(&str) -> B + Copy + Send + 'static,
+    input: &str,
+    year: Year,
+    day: Day,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Vec<PartRecord> {
+    let input: Arc<str> = Arc::from(input);
+    vec![
+        run_record(part_one, &input, year, day, 1, is_timed, timeout),
+        run_record(part_two, &input, year, day, 2, is_timed, timeout),
+    ]
+}
+
+/// Runs the parse step and both parts of a [`Solution`] and returns their records.
+/// If parsing fails, only the record of the parse step is returned.
+pub fn solution_records<S: Solution>(
+    input: &str,
+    year: Year,
+    day: Day,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Vec<PartRecord> {
+    let (parsed, record) = parse_record::<S>(input, year, day, is_timed, timeout);
+    let mut records = vec![record];
+
+    if let Some(parsed) = parsed {
+        records.push(run_record(
+            S::part_one,
+            &parsed,
+            year,
+            day,
+            1,
+            is_timed,
+            timeout,
+        ));
+        records.push(run_record(
+            S::part_two,
+            &parsed,
+            year,
+            day,
+            2,
+            is_timed,
+            timeout,
+        ));
+    }
+
+    records
+}
+
+/// Parses the input of a [`Solution`] and returns the parsed input, if parsing succeeded, along with the record of the parse step.
+fn parse_record<S: Solution>(
+    input: &str,
+    year: Year,
+    day: Day,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> (Option<Arc<S::Parsed>>, PartRecord) {
+    let input: Arc<str> = Arc::from(input);
+
+    let Some((result, base_time, memory)) = execute(S::parse, &input, |parsed| parsed, timeout)
+    else {
+        let record = PartRecord::timeout(year, day, PARSE_PART, timeout.unwrap_or_default());
+        return (None, record);
+    };
+
+    match result {
+        Ok(parsed) => {
+            let stats = if is_timed {
+                bench(S::parse, &input, &base_time, timeout)
+            } else {
+                Stats::single(base_time)
+            };
+            let record = PartRecord::parse_step(year, day, stats).with_memory(memory);
+            (Some(Arc::new(parsed)), record)
+        }
+        Err(outcome) => {
+            let stats = Stats::single(base_time);
+            let record = PartRecord::from_outcome(year, day, PARSE_PART, outcome, stats);
+            (None, record.with_memory(memory))
+        }
+    }
+}
+
+/// Parses a time budget given in (fractional) seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("invalid timeout `{s}`, expecting a positive number of seconds"))
+}
+
+/// The time budget passed to the current process with `--timeout`.
+fn timeout_arg() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == TIMEOUT_FLAG)? + 1;
+
+    match args.get(index).map(|arg| parse_timeout(arg)) {
+        Some(Ok(timeout)) => Some(timeout),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 10");
+            process::exit(1);
+        }
+    }
+}
+
+/// Timing statistics of a benched solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(rename = "mean_ns", with = "protocol::nanos")]
+    pub mean: Duration,
+    #[serde(rename = "median_ns", with = "protocol::nanos")]
+    pub median: Duration,
+    #[serde(rename = "min_ns", with = "protocol::nanos")]
+    pub min: Duration,
+    #[serde(rename = "p95_ns", with = "protocol::nanos")]
+    pub p95: Duration,
+    #[serde(rename = "std_dev_ns", with = "protocol::nanos")]
+    pub std_dev: Duration,
+    /// Number of timed executions, including rejected outliers.
+    pub samples: u128,
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Statistics of a single execution.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            mean: duration,
+            median: duration,
+            min: duration,
+            p95: duration,
+            std_dev: Duration::ZERO,
+            samples: 1,
+            outliers: 0,
+        }
+    }
+
+    /// Computes statistics from raw timings.
+    /// Outliers outside of 1.5 times the interquartile range are rejected before computing the statistics.
+    #[must_use]
+    pub fn from_timings(timings: &[Duration]) -> Self {
+        let mut sorted: Vec<u128> = timings.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = (q3 - q1) * 3 / 2;
+        let kept: Vec<u128> = sorted
+            .iter()
+            .copied()
+            .filter(|&t| t + fence >= q1 && t <= q3 + fence)
+            .collect();
+
+        let count = kept.len() as u128;
+        let mean = kept.iter().sum::<u128>() / count;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = kept
+            .iter()
+            .map(|&t| (t as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            mean: nanos(mean),
+            median: nanos(percentile(&kept, 0.5)),
+            min: nanos(kept[0]),
+            p95: nanos(percentile(&kept, 0.95)),
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            samples: sorted.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn nanos(n: u128) -> Duration {
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(n as u64)
+}
+
+/// Run a solution part. The behavior differs depending on whether the run is timed:
+///  1. if not, the function is executed once.
+///  2. if it is and the part is solved, the function is benched (approx. the `bench.budget` of the config or its `bench.min_samples`, whatever take longer.)
+///     A part with a time budget is only benched for what the first execution left of it.
+///
+/// Returns [`None`] if the first execution exceeds `timeout`.
+fn run_timed<I: ?Sized + Send + Sync + 'static, R: PartOutput>(
+    func: impl Fn(&I) -> R + Copy + Send + 'static,
+    input: &Arc<I>,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    hook: impl Fn(&Outcome),
+) -> Option<(Outcome, Stats, Option<AllocStats>)> {
+    let (result, base_time, memory) = execute(func, input, PartOutput::into_outcome, timeout)?;
+    let result = result.unwrap_or_else(|panic| panic);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    // parts that failed would fail again, so only solved parts are benched.
+    let stats = if is_timed && result.answer().is_some() {
+        bench(func, input, &base_time, timeout)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    Some((result, stats, memory))
+}
+
+/// Executes `func` once, on a separate thread if it has a time budget.
+/// Its result is passed through `convert` on the same thread, so it does not need to be [`Send`].
+/// Returns [`None`] if the execution exceeds `timeout`.
+fn execute<I: ?Sized + Send + Sync + 'static, T, U: Send + 'static>(
+    func: impl Fn(&I) -> T + Send + 'static,
+    input: &Arc<I>,
+    convert: impl FnOnce(T) -> U + Send + 'static,
+    timeout: Option<Duration>,
+) -> Option<(Result<U, Outcome>, Duration, Option<AllocStats>)> {
+    match timeout {
+        Some(timeout) => run_with_timeout(func, Arc::clone(input), convert, timeout),
+        None => Some(run_once(func, input, convert)),
+    }
+}
+
+/// Executes `func` once, measuring its time and, if enabled, its heap usage.
+/// A panic is caught and turned into its outcome.
+fn run_once<I: ?Sized, T, U>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    convert: impl FnOnce(T) -> U,
+) -> (Result<U, Outcome>, Duration, Option<AllocStats>) {
+    let ((result, duration), memory) = alloc::measure(|| {
+        let timer = Instant::now();
+        let result = outcome::catch(|| black_box(func(black_box(input))));
+        (result, timer.elapsed())
+    });
+    (result.map(convert), duration, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Executes `func` once on a separate thread and stops waiting for it after `timeout`.
+/// Threads can not be cancelled, so a part that timed out keeps running in the background until the process exits.
+/// This is why `all` runs days with a time budget in their own process.
+fn run_with_timeout<I: ?Sized + Send + Sync + 'static, T, U: Send + 'static>(
+    func: impl Fn(&I) -> T + Send + 'static,
+    input: Arc<I>,
+    convert: impl FnOnce(T) -> U + Send + 'static,
+    timeout: Duration,
+) -> Option<(Result<U, Outcome>, Duration, Option<AllocStats>)> {
+    let (tx, rx) = mpsc::channel();
+    let timer = Instant::now();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let _ = tx.send(run_once(func, &input, convert));
+        })
+        .expect("could not spawn solution thread");
 
+    match rx.recv_timeout(timeout) {
+        Ok(run) => Some(run),
+        Err(RecvTimeoutError::Timeout) => None,
+        // panics of the part are caught, so only a panic outside of it ends the thread without a result.
+        Err(RecvTimeoutError::Disconnected) => Some((
+            Err(Outcome::Panic {
+                message: "the part stopped without a result".into(),
+                location: None,
+            }),
+            timer.elapsed(),
+            None,
+        )),
+    }
+}
+
+/// Benches `func`, within what is left of `timeout` after its first execution took `base_time`.
+/// If no execution fits into that, the first execution is all there is to report.
+fn bench<I: ?Sized, T>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    base_time: &Duration,
+    timeout: Option<Duration>,
+) -> Stats {
+    let settings = &config::get().bench;
     let bench_iterations = cmp::min(
-        10000,
+        settings.max_samples.into(),
         cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
+            settings.budget().as_nanos() / cmp::max(base_time.as_nanos(), 10),
+            settings.min_samples.into(),
         ),
     );
 
+    // an execution is only started if it is expected to end within the time budget.
+    let deadline = timeout.map(|timeout| Instant::now() + timeout.saturating_sub(*base_time));
+    let has_time = || deadline.is_none_or(|deadline| Instant::now() + *base_time <= deadline);
+
+    // warm up caches and branch predictors before measuring.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 1000);
+
+    for _ in 0..warmup_iterations {
+        if !has_time() {
+            break;
+        }
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
+        if !has_time() {
+            break;
+        }
         let timer = Instant::now();
-        func(cloned);
+        black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    if timers.is_empty() {
+        return Stats::single(*base_time);
+    }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_timings(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+pub(crate) fn format_duration(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} ±{:.1?}, min {:.1?}, p95 {:.1?} @ {} samples)",
+            stats.median, stats.std_dev, stats.min, stats.p95, stats.samples
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Describes the heap usage of a part, or nothing if it was not measured.
+pub(crate) fn format_memory(memory: Option<&AllocStats>) -> String {
+    memory.map_or_else(String::new, |m| {
+        format!(
+            " [{} allocs, {} total, {} peak]",
+            m.allocations,
+            alloc::format_bytes(m.bytes),
+            alloc::format_bytes(m.peak_bytes)
+        )
+    })
+}
+
+pub(crate) fn print_timeout(part: &str, timeout: Duration) {
+    print!("\r");
+    println!("{part}: ⏱ timeout after {timeout:.1?}");
+}
+
+pub(crate) fn print_result(result: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Outcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -116,12 +537,20 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unimplemented => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                print!("{part}: {result}");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: {result}             ");
+            }
+        }
+        Outcome::Error(_) | Outcome::Panic { .. } => {
+            if is_intermediate_result {
+                print!("{part}: {result}");
+            } else {
+                print!("\r");
+                println!("{part}: {result}{duration_str}");
             }
         }
     }
@@ -129,12 +558,17 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the answer ledger does not know the answer to be wrong.
+///  4. the answer was computed from the puzzle input.
+///
+/// If the server rate-limits the answer, it is submitted again after a countdown, see [`submissions::submit`].
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, submissions::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +591,239 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if input::is_custom_input() {
+        eprintln!("Refusing to submit an answer that was not computed from the puzzle input.");
+        return None;
+    }
+
+    let answer = result.to_string();
+
+    let mut ledger = match Ledger::load(paths::ledger_path(year)) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Failed to load answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(rejection) = ledger.check(day, part, &answer) {
+        eprintln!("Refusing to submit {answer}: {rejection}");
+        return None;
+    }
+
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let outcome = submissions::submit(
+        &client,
+        &mut SystemClock,
+        paths::queue_path(),
+        Pending::new(year, day, part, &answer),
+        &mut stdout(),
+    );
+
+    match &outcome {
+        Ok(outcome) => {
+            println!("{outcome}");
+            ledger.record(day, part, &answer, outcome);
+            if let Err(e) = ledger.save(paths::ledger_path(year)) {
+                eprintln!("Failed to record answer: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(outcome)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, parse_timeout, run_timed, solution_records, Stats};
+    use crate::template::{outcome::Outcome, protocol::PartStatus, solution::Solution};
+    use crate::{day, year};
+    use std::{sync::Arc, time::Duration};
+
+    fn input(s: &str) -> Arc<str> {
+        Arc::from(s)
+    }
+
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Parsed = Vec<u32>;
+        type PartOne = Option<u32>;
+        type PartTwo = Result<u32, String>;
+
+        fn parse(input: &str) -> Vec<u32> {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part_one(numbers: &Vec<u32>) -> Option<u32> {
+            Some(numbers.iter().sum())
+        }
+
+        fn part_two(numbers: &Vec<u32>) -> Result<u32, String> {
+            numbers.iter().max().copied().ok_or("no numbers".into())
+        }
+    }
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_timings(&millis(&[4, 2, 3, 1, 5]));
+
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.std_dev.as_micros(), 1414);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_timings(&millis(&[10, 11, 10, 12, 11, 10, 11, 500]));
+
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.p95, Duration::from_millis(12));
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = Stats::from_timings(&millis(&[4, 2, 3, 1, 5]));
+        assert_eq!(
+            format_duration(&stats),
+            " (3.0ms ±1.4ms, min 1.0ms, p95 5.0ms @ 5 samples)"
+        );
+        assert_eq!(
+            format_duration(&Stats::single(Duration::from_micros(15))),
+            " (15.0µs)"
+        );
+    }
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("0.5"), Ok(Duration::from_millis(500)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("-1").is_err());
+        assert!(parse_timeout("ten").is_err());
+    }
+
+    #[test]
+    fn gives_up_on_parts_over_budget() {
+        let slow = |input: &str| {
+            std::thread::sleep(Duration::from_secs(5));
+            Some(input.len())
+        };
+        let fast = |input: &str| Some(input.len());
+
+        assert!(run_timed(
+            slow,
+            &input("abc"),
+            false,
+            Some(Duration::from_millis(20)),
+            |_| {}
+        )
+        .is_none());
+
+        let (result, _, _) = run_timed(
+            fast,
+            &input("abc"),
+            false,
+            Some(Duration::from_secs(5)),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(result, Outcome::Solved("3".into()));
+    }
+
+    #[test]
+    fn benches_within_time_budget() {
+        let slow = |input: &str| {
+            std::thread::sleep(Duration::from_millis(40));
+            Some(input.len())
+        };
+
+        // benching stops at the budget, instead of running the minimum number of samples.
+        let timer = std::time::Instant::now();
+        let (_, stats, _) = run_timed(
+            slow,
+            &input("abc"),
+            true,
+            Some(Duration::from_millis(150)),
+            |_| {},
+        )
+        .unwrap();
+        assert!(timer.elapsed() < Duration::from_millis(300));
+        assert!(stats.samples < 3);
+
+        // a first run that used most of the budget is not benched.
+        let (_, stats, _) = run_timed(
+            slow,
+            &input("abc"),
+            true,
+            Some(Duration::from_millis(60)),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(stats.samples, 1);
+    }
+
+    #[test]
+    fn reports_failing_parts() {
+        let parse = |input: &str| input.parse::<u32>();
+        let (result, stats, _) = run_timed(parse, &input("abc"), true, None, |_| {}).unwrap();
+        assert_eq!(
+            result,
+            Outcome::Error("invalid digit found in string".into())
+        );
+        assert_eq!(stats.samples, 1);
+
+        let panics = |input: &str| -> Option<u32> { panic!("bad input {input}") };
+        let (result, _, _) = run_timed(
+            panics,
+            &input("abc"),
+            false,
+            Some(Duration::from_secs(5)),
+            |_| {},
+        )
+        .unwrap();
+        assert!(matches!(
+            result,
+            Outcome::Panic { message, location: Some(_) } if message == "bad input abc"
+        ));
+    }
+
+    #[test]
+    fn runs_parse_and_parts_separately() {
+        let records = solution_records::<Numbers>("1\n5\n3", year!(2023), day!(1), true, None);
+
+        assert_eq!(records.len(), 3);
+        assert!(records[0].is_parse_step());
+        assert_eq!(records[0].status, PartStatus::Solved);
+        assert!(records[0].timing.samples > 1);
+        assert_eq!(records[1].answer, Some("9".into()));
+        assert_eq!(records[2].answer, Some("5".into()));
+        assert!(records[2].timing.samples > 1);
+    }
+
+    #[test]
+    fn skips_parts_if_parsing_fails() {
+        let records = solution_records::<Numbers>("1\nx", year!(2023), day!(1), false, None);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, PartStatus::Panic);
+    }
 }
@@ -0,0 +1,31 @@
+/// An alternative to free `part_one` and `part_two` functions for days that share a parsed input between both parts.
+/// The input is parsed once, parsing and each part are timed and benchmarked independently.
+///
+/// ```ignore
+/// advent_of_code::solution!(2023, 4, Day04);
+///
+/// pub struct Day04;
+///
+/// impl advent_of_code::Solution for Day04 {
+///     type Parsed = Vec<Card>;
+///     type PartOne = Option<u32>;
+///     type PartTwo = Option<u32>;
+///
+///     fn parse(input: &str) -> Vec<Card> { ... }
+///     fn part_one(cards: &Vec<Card>) -> Option<u32> { ... }
+///     fn part_two(cards: &Vec<Card>) -> Option<u32> { ... }
+/// }
+/// ```
+use crate::template::outcome::PartOutput;
+
+pub trait Solution: 'static {
+    /// The parsed input, shared by both parts.
+    type Parsed: Send + Sync + 'static;
+    /// The return type of part one, e.g. `Option<u32>` or `Result<u32, String>`.
+    type PartOne: PartOutput;
+    type PartTwo: PartOutput;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne;
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo;
+}
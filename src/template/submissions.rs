@@ -0,0 +1,313 @@
+/// Module that waits out the rate limit of the Advent of Code server before submitting an answer again.
+/// Answers that wait are kept in a queue file, so a wait that was interrupted can be resumed with `submit --flush`.
+use std::{
+    fmt::Display,
+    fs, io,
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{
+    aoc_client::{AocClient, AocClientError, SubmissionOutcome},
+    clock::{self, Clock},
+};
+use crate::{Day, Year};
+
+/// The least time to wait after a rate limit, in case the server does not say how long.
+const MIN_COOLDOWN: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+    Client(AocClientError),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<AocClientError> for Error {
+    fn from(e: AocClientError) -> Self {
+        Error::Client(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse submission queue: {e}"),
+            Error::IO(e) => write!(f, "could not access submission queue: {e}"),
+            Error::Client(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// An answer that waits for the rate limit of the server to pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pending {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Unix timestamp in seconds from which on the server accepts answers again.
+    pub not_before: u64,
+}
+
+impl Pending {
+    /// An answer that can be submitted right away.
+    #[must_use]
+    pub fn new(year: Year, day: Day, part: u8, answer: &str) -> Self {
+        Self {
+            year: year.into_inner(),
+            day: day.into_inner(),
+            part,
+            answer: answer.into(),
+            not_before: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn year(&self) -> Option<Year> {
+        Year::new(self.year)
+    }
+
+    #[must_use]
+    pub fn day(&self) -> Option<Day> {
+        Day::new(self.day)
+    }
+
+    #[must_use]
+    pub fn not_before(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.not_before)
+    }
+
+    fn is_same_part(&self, other: &Pending) -> bool {
+        (self.year, self.day, self.part) == (other.year, other.day, other.part)
+    }
+}
+
+impl Display for Pending {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} day {:02} part {}: {}",
+            self.year, self.day, self.part, self.answer
+        )
+    }
+}
+
+/// The answers that wait for a rate limit to pass, in the order they were queued.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Queue {
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Pending>,
+}
+
+impl Queue {
+    /// Reads the queue at `path`, a missing file is treated as an empty queue.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => toml::from_str(&s).map_err(|e| Error::Parser(e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the queue to `path`, an empty queue removes the file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+
+        if self.submissions.is_empty() {
+            return match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let s = toml::to_string(self).map_err(|e| Error::Parser(e.to_string()))?;
+        fs::write(path, s)?;
+        Ok(())
+    }
+
+    /// Queues an answer, replacing an answer to the same part that was queued before.
+    pub fn push(&mut self, pending: Pending) {
+        match self
+            .submissions
+            .iter_mut()
+            .find(|p| p.is_same_part(&pending))
+        {
+            Some(queued) => *queued = pending,
+            None => self.submissions.push(pending),
+        }
+    }
+
+    pub fn remove(&mut self, pending: &Pending) {
+        self.submissions.retain(|p| !p.is_same_part(pending));
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Submits an answer, once its cooldown has passed.
+/// If the server rate-limits the answer, it is queued at `queue_path` and submitted again after a countdown on `out`.
+/// Returns the first verdict that is not a rate limit, which also removes the answer from the queue.
+pub fn submit(
+    client: &AocClient,
+    clock: &mut impl Clock,
+    queue_path: impl AsRef<Path>,
+    mut pending: Pending,
+    out: &mut impl Write,
+) -> Result<SubmissionOutcome, Error> {
+    let queue_path = queue_path.as_ref();
+    let (Some(year), Some(day)) = (pending.year(), pending.day()) else {
+        return Err(Error::Parser(format!("invalid submission \"{pending}\"")));
+    };
+
+    loop {
+        if clock.now() < pending.not_before() {
+            clock::countdown(clock, pending.not_before(), "Submitting again in", out)?;
+            writeln!(out)?;
+        }
+
+        let outcome = client.submit(year, day, pending.part, &pending.answer)?;
+
+        let mut queue = Queue::load(queue_path)?;
+
+        if let SubmissionOutcome::RateLimited { wait } = outcome {
+            pending.not_before = unix_seconds(clock.now() + wait.max(MIN_COOLDOWN));
+            queue.push(pending.clone());
+            queue.save(queue_path)?;
+
+            writeln!(out, "{outcome}")?;
+            writeln!(
+                out,
+                "Queued the answer in \"{}\", interrupt the countdown to submit it later with `cargo submit --flush`.",
+                queue_path.display()
+            )?;
+            continue;
+        }
+
+        queue.remove(&pending);
+        queue.save(queue_path)?;
+        return Ok(outcome);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        env, fs, process,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use super::{submit, Pending, Queue};
+    use crate::template::{
+        aoc_client::{stand_in::serve_all, AocClient, SubmissionOutcome},
+        clock::FakeClock,
+    };
+    use crate::{day, year};
+
+    const RATE_LIMITED: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+    const CORRECT: &str =
+        "<article><p>That's the right answer!  You are one gold star closer.</p></article>";
+
+    fn queue_path(name: &str) -> String {
+        env::temp_dir()
+            .join(format!("aoc_queue_{name}_{}.toml", process::id()))
+            .display()
+            .to_string()
+    }
+
+    #[test]
+    fn waits_out_rate_limits() {
+        let (base_url, rx) = serve_all(vec![(200, RATE_LIMITED), (200, CORRECT)]);
+        let client = AocClient::new("secret").with_base_url(&base_url);
+        let mut clock = FakeClock::at(UNIX_EPOCH + Duration::from_secs(1_000));
+        let path = queue_path("wait");
+        let mut out = vec![];
+
+        let outcome = submit(
+            &client,
+            &mut clock,
+            &path,
+            Pending::new(year!(2023), day!(1), 2, "42"),
+            &mut out,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(outcome, SubmissionOutcome::Correct);
+        assert_eq!(clock.now, UNIX_EPOCH + Duration::from_secs(1_065));
+        assert!(out.contains("You gave an answer too recently, wait 65s"));
+        assert!(out.contains("Submitting again in 00:01:05"));
+        assert!(out.contains("Submitting again in 00:00:01"));
+
+        assert!(rx.recv().unwrap().ends_with("level=2&answer=42"));
+        assert!(rx.recv().unwrap().ends_with("level=2&answer=42"));
+        assert!(fs::metadata(&path).is_err());
+    }
+
+    #[test]
+    fn keeps_answers_queued_until_a_verdict() {
+        let (base_url, _rx) = serve_all(vec![(200, RATE_LIMITED), (500, "")]);
+        let client = AocClient::new("secret").with_base_url(&base_url);
+        let mut clock = FakeClock::at(UNIX_EPOCH + Duration::from_secs(1_000));
+        let path = queue_path("keep");
+
+        let result = submit(
+            &client,
+            &mut clock,
+            &path,
+            Pending::new(year!(2023), day!(1), 1, "7"),
+            &mut vec![],
+        );
+        assert!(result.is_err());
+
+        let queue = Queue::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            queue.submissions,
+            vec![Pending {
+                year: 2023,
+                day: 1,
+                part: 1,
+                answer: "7".into(),
+                not_before: 1_065,
+            }]
+        );
+    }
+
+    #[test]
+    fn replaces_answers_of_the_same_part() {
+        let mut queue = Queue::default();
+        queue.push(Pending::new(year!(2023), day!(1), 1, "7"));
+        queue.push(Pending::new(year!(2023), day!(1), 2, "8"));
+        queue.push(Pending::new(year!(2023), day!(1), 1, "9"));
+
+        let answers: Vec<_> = queue
+            .submissions
+            .iter()
+            .map(|p| p.answer.as_str())
+            .collect();
+        assert_eq!(answers, ["9", "8"]);
+
+        queue.remove(&Pending::new(year!(2023), day!(1), 1, "9"));
+        assert_eq!(queue.submissions.len(), 1);
+
+        let s = toml::to_string(&queue).unwrap();
+        assert!(s.starts_with("[[submission]]"));
+        assert_eq!(toml::from_str::<Queue>(&s).unwrap(), queue);
+    }
+}
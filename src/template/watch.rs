@@ -0,0 +1,90 @@
+/// Detects changes to files by polling their modification times.
+/// Only uses std, so it works without any platform specific file watching APIs.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    /// Starts watching `paths`, files that do not exist yet are watched for their creation.
+    pub fn new(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.into();
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    /// Returns the files that were modified, created or removed since the last poll.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        self.files
+            .iter_mut()
+            .filter_map(|(path, last_modified)| {
+                let modified = modified(path);
+                if modified == *last_modified {
+                    return None;
+                }
+                *last_modified = modified;
+                Some(path.clone())
+            })
+            .collect()
+    }
+
+    /// Blocks until at least one file changed, checking every `interval`.
+    pub fn wait(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            let changed = self.poll();
+            if !changed.is_empty() {
+                return changed;
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Watcher;
+    use std::{
+        env, fs,
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc_watch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("01.txt");
+        let missing = dir.join("02.txt");
+        fs::write(&file, "a").unwrap();
+
+        let mut watcher = Watcher::new([&file, &missing]);
+        assert!(watcher.poll().is_empty());
+
+        let f = fs::File::options().write(true).open(&file).unwrap();
+        f.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(watcher.poll(), vec![file.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&missing, "b").unwrap();
+        assert_eq!(watcher.wait(Duration::from_millis(1)), vec![missing]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+//! Runs the `all` command of the built binary against the solutions in `src/bin`.
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+    time::Duration,
+};
+
+/// The example of 2023 day 5, whose solution prints while it runs.
+const DAY_05: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+/// The example of 2023 day 6, which runs at the same time as day 5 with `--jobs`.
+const DAY_06: &str = "Time:      7  15   30
+Distance:  9  40  200
+";
+
+/// A variant of 2023 day 6 whose second part takes minutes, while the first one is quick.
+const DAY_06_SLOW: &str = "Time:      8000  000000
+Distance:  1  0
+";
+
+/// Creates a data directory with the given inputs of 2023.
+fn data_dir(name: &str, inputs: &[(&str, &str)]) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc_all_{name}_{}", std::process::id()));
+    let inputs_dir = dir.join("2023/inputs");
+    fs::create_dir_all(&inputs_dir).unwrap();
+
+    for (day, input) in inputs {
+        fs::write(inputs_dir.join(format!("{day}.txt")), input).unwrap();
+    }
+
+    dir
+}
+
+fn run_all(data_dir: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_advent_of_code"))
+        .args(["all", "--year", "2023"])
+        .args(args)
+        .env("AOC_DATA_DIR", data_dir)
+        .output()
+        .unwrap()
+}
+
+/// Checks that stdout of `all --json` is nothing but the records of day 5.
+#[cfg(unix)]
+fn assert_json_output(output: Output) {
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<serde_json::Value> = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout is not valid JSON ({e}):\n{stdout}"));
+
+    let answers: Vec<_> = records
+        .iter()
+        .map(|r| (r["day"].as_u64(), r["part"].as_u64(), r["answer"].as_str()))
+        .collect();
+    assert_eq!(
+        answers,
+        vec![
+            (Some(5), Some(1), Some("35")),
+            (Some(5), Some(2), Some("46"))
+        ]
+    );
+
+    // what the solution printed is still shown, on stderr.
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Seeds: [79, 14, 55, 13]"));
+}
+
+#[test]
+#[cfg(unix)]
+fn prints_only_records_in_json_mode() {
+    let dir = data_dir("json", &[("05", DAY_05)]);
+    let output = run_all(&dir, &["--json"]);
+    fs::remove_dir_all(dir).unwrap();
+
+    assert_json_output(output);
+}
+
+#[test]
+#[cfg(unix)]
+fn prints_only_records_of_concurrent_days_in_json_mode() {
+    let dir = data_dir("json_jobs", &[("05", DAY_05)]);
+    let output = run_all(&dir, &["--json", "--jobs", "4"]);
+    fs::remove_dir_all(dir).unwrap();
+
+    assert_json_output(output);
+}
+
+#[test]
+fn groups_output_of_concurrent_days() {
+    let dir = data_dir("jobs", &[("05", DAY_05), ("06", DAY_06)]);
+    let output = run_all(&dir, &["--jobs", "4"]);
+    fs::remove_dir_all(dir).unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let section = |day: &str| {
+        let start = stdout.find(&format!("Day {day}")).unwrap();
+        let end = stdout[start..]
+            .find(&format!("Day {:02}", day.parse::<u8>().unwrap() + 1))
+            .map_or(stdout.len(), |i| start + i);
+        &stdout[start..end]
+    };
+
+    // everything day 5 printed follows its header and comes before its answers.
+    let day_05 = section("05");
+    let lines = day_05
+        .lines()
+        .skip_while(|line| !line.starts_with("Seeds: "))
+        .take_while(|line| !line.starts_with("Part 1"));
+    assert_eq!(
+        lines.filter(|line| line.contains("Seed")).count(),
+        1 + 4 + 27
+    );
+    assert!(day_05.contains("35"));
+
+    assert!(!section("06").contains("Seed"));
+    assert!(section("06").contains("288"));
+    assert!(!stdout[..stdout.find("Day 05").unwrap()].contains("Seed"));
+}
+
+/// Reads the CPU time of a process and the CPU time of its children that it waited for from `/proc/<pid>/stat`.
+#[cfg(target_os = "linux")]
+fn cpu_times(pid: u32) -> (Duration, Duration) {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).unwrap();
+    // the fields after the command name, which is in parentheses and may contain spaces.
+    let fields: Vec<u64> = stat[stat.rfind(')').unwrap() + 2..]
+        .split_whitespace()
+        .skip(1)
+        .map(|field| field.parse().unwrap_or_default())
+        .collect();
+
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as f64;
+    let time = |a: u64, b: u64| Duration::from_secs_f64((a + b) as f64 / ticks);
+    (time(fields[10], fields[11]), time(fields[12], fields[13]))
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn stops_parts_over_budget_with_their_day() {
+    let dir = data_dir("timeout", &[("06", DAY_06_SLOW)]);
+    let child = Command::new(env!("CARGO_BIN_EXE_advent_of_code"))
+        .args(["all", "--year", "2023", "--timeout", "0.5"])
+        .env("AOC_DATA_DIR", &dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // wait for `all` to exit, but keep it around as a zombie to read what it used.
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    let result = unsafe {
+        libc::waitid(
+            libc::P_PID,
+            child.id(),
+            &mut info,
+            libc::WEXITED | libc::WNOWAIT,
+        )
+    };
+    assert_eq!(result, 0);
+    let (own, children) = cpu_times(child.id());
+
+    let output = child.wait_with_output().unwrap();
+    fs::remove_dir_all(dir).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("timeout"), "{stdout}");
+
+    // the part ran for its budget in the process of its day, which was done before the part was reported.
+    // a part that kept running in the background would have used the CPU of `all` itself.
+    assert!(own < Duration::from_millis(250), "`all` used {own:?}");
+    assert!(
+        children >= Duration::from_millis(400),
+        "the day used {children:?}"
+    );
+}
@@ -0,0 +1,62 @@
+//! Builds a copy of the project in which one solution does not compile.
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Output},
+};
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let path = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &path);
+        } else {
+            fs::copy(entry.path(), path).unwrap();
+        }
+    }
+}
+
+fn cargo_check(manifest_dir: &Path, bin: &str) -> Output {
+    Command::new(env!("CARGO"))
+        .args(["check", "--quiet", "--offline", "--bin", bin])
+        .current_dir(manifest_dir)
+        // the target dir is kept between runs, so dependencies are only checked once.
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("broken_day_target"),
+        )
+        .env_remove("AOC_CONFIG")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn builds_commands_with_a_broken_solution() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("broken_day");
+    let _ = fs::remove_dir_all(&dir);
+
+    copy_dir(&root.join("src"), &dir.join("src"));
+    for file in ["Cargo.toml", "Cargo.lock", "build.rs"] {
+        fs::copy(root.join(file), dir.join(file)).unwrap();
+    }
+    fs::write(
+        dir.join("src/bin/2023_01.rs"),
+        "advent_of_code::solution!(2023, 1);\n\npub fn part_one(input: &str) -> Option<u32> {\n    input.len()\n}\n",
+    )
+    .unwrap();
+
+    // the broken day only breaks the runner of `all` and `verify`.
+    let output = cargo_check(&dir, "advent_of_code");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = cargo_check(&dir, "advent_of_code_runner");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("src/bin/2023_01.rs"));
+}
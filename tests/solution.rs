@@ -0,0 +1,71 @@
+//! Expands the `solution!` macro the way the days in `src/bin` do.
+use std::time::Duration;
+
+#[allow(dead_code)]
+mod whole_seconds {
+    advent_of_code::solution!(2023, 1, timeout = 30);
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    pub fn part_two(_input: &str) -> Option<usize> {
+        None
+    }
+}
+
+#[allow(dead_code)]
+mod fractional_seconds {
+    advent_of_code::solution!(2023, 2, timeout = 0.5);
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    pub fn part_two(_input: &str) -> Option<usize> {
+        None
+    }
+}
+
+#[allow(dead_code)]
+mod solution_type {
+    use advent_of_code::Solution;
+
+    advent_of_code::solution!(2023, 3, Day03, timeout = 1.5);
+
+    pub struct Day03;
+
+    impl Solution for Day03 {
+        type Parsed = usize;
+        type PartOne = Option<usize>;
+        type PartTwo = Option<usize>;
+
+        fn parse(input: &str) -> Self::Parsed {
+            input.len()
+        }
+
+        fn part_one(parsed: &Self::Parsed) -> Self::PartOne {
+            Some(*parsed)
+        }
+
+        fn part_two(_parsed: &Self::Parsed) -> Self::PartTwo {
+            None
+        }
+    }
+}
+
+#[test]
+fn takes_timeouts_in_seconds() {
+    assert_eq!(
+        whole_seconds::SOLUTION.timeout,
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(
+        fractional_seconds::SOLUTION.timeout,
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(
+        solution_type::SOLUTION.timeout,
+        Some(Duration::from_millis(1500))
+    );
+}